@@ -1,5 +1,9 @@
 use std::collections::BTreeMap;
+use std::time::Duration;
 use crate::keybinds::{KeybindManager, KeyAction, parse_key_strings};
+use crate::session::SessionOrder;
+use crate::zoxide::{DirectoryFilter, DirectorySource, MissingDirectories, NamingStrategy, SortMode, ZoxideCommand};
+use crate::zoxide::command::parse_env_overrides;
 
 /// Plugin configuration loaded from Zellij layout
 #[derive(Debug, Clone)]
@@ -8,15 +12,57 @@ pub struct Config {
     pub default_layout: Option<String>,
     /// Separator used in session names (default: ".")
     pub session_separator: String,
+    /// How session names are generated for directories (default: smart)
+    pub naming_strategy: NamingStrategy,
+    /// Minimum age of exited sessions selected by prune (default: 7 days)
+    pub prune_older_than: Duration,
+    /// How zoxide is invoked (binary, extra query arguments, environment)
+    pub zoxide: ZoxideCommand,
+    /// Path of zoxide's `db.zo` relative to the host folder, read when commands can't be run
+    pub zoxide_database: Option<String>,
+    /// Sources of candidate directories, in priority order (zoxide first)
+    pub directory_sources: Vec<DirectorySource>,
+    /// Exclude/include patterns and minimum zoxide score applied to listed directories
+    pub directory_filter: DirectoryFilter,
+    /// Whether directories that no longer exist are dimmed or hidden
+    pub missing_directories: MissingDirectories,
+    /// Initial order of listed directories (switchable at runtime)
+    pub sort_mode: SortMode,
+    /// Order of live and exited sessions in the main list
+    pub session_order: SessionOrder,
+    /// Interval between background refreshes of the directory list (zero disables them)
+    pub refresh_interval: Duration,
+    /// Run `zoxide add` for directories opened through ZSM (default: true)
+    pub zoxide_add_on_switch: bool,
     /// Keybind configuration
     pub keybinds: KeybindManager,
 }
 
+/// Default depth below each project root scanned for project markers
+const DEFAULT_PROJECT_SCAN_DEPTH: usize = 3;
+
+/// Default interval between background refreshes of the directory list
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Default age after which exited sessions are pruned
+const DEFAULT_PRUNE_OLDER_THAN: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             default_layout: None,
             session_separator: ".".to_string(),
+            naming_strategy: NamingStrategy::default(),
+            prune_older_than: DEFAULT_PRUNE_OLDER_THAN,
+            zoxide: ZoxideCommand::default(),
+            zoxide_database: None,
+            directory_sources: vec![DirectorySource::Zoxide],
+            directory_filter: DirectoryFilter::default(),
+            missing_directories: MissingDirectories::default(),
+            sort_mode: SortMode::default(),
+            session_order: SessionOrder::default(),
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            zoxide_add_on_switch: true,
             keybinds: KeybindManager::new(),
         }
     }
@@ -36,17 +82,165 @@ impl Config {
             eprintln!("Error: {}", error);
         }
         
+        let prune_older_than = match config.get("prune_older_than").map(|s| parse_duration(s)) {
+            Some(Ok(duration)) => duration,
+            Some(Err(err)) => {
+                eprintln!("Warning: Invalid prune_older_than: {}", err);
+                DEFAULT_PRUNE_OLDER_THAN
+            }
+            None => DEFAULT_PRUNE_OLDER_THAN,
+        };
+        
+        let refresh_interval = match config.get("refresh_interval").map(|s| parse_duration(s)) {
+            Some(Ok(duration)) => duration,
+            Some(Err(err)) => {
+                eprintln!("Warning: Invalid refresh_interval: {}", err);
+                DEFAULT_REFRESH_INTERVAL
+            }
+            None => DEFAULT_REFRESH_INTERVAL,
+        };
+        
+        let missing_directories = match config.get("missing_directories").map(|s| s.as_str()) {
+            Some("dim") | None => MissingDirectories::Dim,
+            Some("hide") => MissingDirectories::Hide,
+            Some(other) => {
+                eprintln!("Warning: Invalid missing_directories '{}', expected dim or hide", other);
+                MissingDirectories::Dim
+            }
+        };
+        
+        let sort_mode = match config.get("sort_mode") {
+            Some(value) => SortMode::parse(value).unwrap_or_else(|| {
+                eprintln!("Warning: Invalid sort_mode '{}', expected frequency, recency or frecency", value);
+                SortMode::default()
+            }),
+            None => SortMode::default(),
+        };
+        
+        let naming_strategy = match config.get("naming_strategy").map(|s| NamingStrategy::parse(s)) {
+            Some(Ok(strategy)) => strategy,
+            Some(Err(err)) => {
+                eprintln!("Warning: Invalid naming_strategy: {}", err);
+                NamingStrategy::default()
+            }
+            None => NamingStrategy::default(),
+        };
+        
+        let session_order = match config.get("session_order").map(|s| s.as_str()) {
+            Some("default") | None => SessionOrder::Default,
+            Some("mru") => SessionOrder::Mru,
+            Some(other) => {
+                eprintln!("Warning: Invalid session_order '{}', expected default or mru", other);
+                SessionOrder::Default
+            }
+        };
+        
         Self {
             default_layout: config.get("default_layout").cloned(),
             session_separator: config
                 .get("session_separator")
                 .cloned()
                 .unwrap_or_else(|| ".".to_string()),
+            naming_strategy,
+            prune_older_than,
+            zoxide: parse_zoxide_command(config),
+            zoxide_database: config.get("zoxide_database").cloned(),
+            directory_sources: parse_directory_sources(config),
+            directory_filter: parse_directory_filter(config),
+            missing_directories,
+            sort_mode,
+            session_order,
+            refresh_interval,
+            zoxide_add_on_switch: config.get("zoxide_add_on_switch").map(|s| s.as_str()) != Some("false"),
             keybinds,
         }
     }
 }
 
+/// Get a whitespace-separated list option (empty when not set)
+fn split_list(config: &BTreeMap<String, String>, key: &str) -> Vec<String> {
+    config
+        .get(key)
+        .map(|value| value.split_whitespace().map(|s| s.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// Build the zoxide invocation from the config map
+fn parse_zoxide_command(config: &BTreeMap<String, String>) -> ZoxideCommand {
+    let mut zoxide = ZoxideCommand {
+        query_args: split_list(config, "zoxide_query_args"),
+        ..ZoxideCommand::default()
+    };
+    if let Some(binary) = config.get("zoxide_path").map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        zoxide.binary = binary.to_string();
+    }
+    if let Some(env) = config.get("zoxide_env") {
+        match parse_env_overrides(env) {
+            Ok(env) => zoxide.env = env,
+            Err(err) => eprintln!("Warning: Invalid zoxide_env: {}", err),
+        }
+    }
+    zoxide
+}
+
+/// Build the list of directory sources from the config map
+fn parse_directory_sources(config: &BTreeMap<String, String>) -> Vec<DirectorySource> {
+    let mut sources = vec![DirectorySource::Zoxide];
+    
+    let bookmarks = split_list(config, "bookmarks");
+    if !bookmarks.is_empty() {
+        sources.push(DirectorySource::Bookmarks(bookmarks));
+    }
+    let glob_roots = split_list(config, "glob_roots");
+    if !glob_roots.is_empty() {
+        sources.push(DirectorySource::Glob(glob_roots));
+    }
+    let project_roots = split_list(config, "project_roots");
+    if !project_roots.is_empty() {
+        let depth = match config.get("project_scan_depth").map(|s| s.trim().parse::<usize>()) {
+            Some(Ok(depth)) => depth,
+            Some(Err(_)) => {
+                eprintln!("Warning: Invalid project_scan_depth, using {}", DEFAULT_PROJECT_SCAN_DEPTH);
+                DEFAULT_PROJECT_SCAN_DEPTH
+            }
+            None => DEFAULT_PROJECT_SCAN_DEPTH,
+        };
+        let base_score = match config.get("project_base_score").map(|s| s.trim().parse::<f64>()) {
+            Some(Ok(score)) if score.is_finite() => score,
+            Some(_) => {
+                eprintln!("Warning: Invalid project_base_score, using 0");
+                0.0
+            }
+            None => 0.0,
+        };
+        sources.push(DirectorySource::Projects { roots: project_roots, depth, base_score });
+    }
+    match config.get("ghq").map(|s| s.as_str()) {
+        Some("true") => sources.push(DirectorySource::Ghq),
+        Some("false") | None => {}
+        Some(other) => eprintln!("Warning: Invalid ghq value '{}', expected true or false", other),
+    }
+    if let Some(command) = config.get("directory_command").filter(|c| !c.trim().is_empty()) {
+        sources.push(DirectorySource::Command(command.clone()));
+    }
+    
+    sources
+}
+
+/// Build the directory filter from the config map
+fn parse_directory_filter(config: &BTreeMap<String, String>) -> DirectoryFilter {
+    let min_score = match config.get("min_score").map(|s| s.trim().parse::<f64>()) {
+        Some(Ok(score)) if score.is_finite() => Some(score),
+        Some(_) => {
+            eprintln!("Warning: Invalid min_score, ignoring it");
+            None
+        }
+        None => None,
+    };
+    
+    DirectoryFilter::new(split_list(config, "exclude_patterns"), split_list(config, "include_patterns"), min_score)
+}
+
 /// Parse keybind configuration from the config map
 fn parse_keybind_config(keybinds: &mut KeybindManager, config: &BTreeMap<String, String>) {
     // Map of config keys to actions
@@ -55,6 +249,16 @@ fn parse_keybind_config(keybinds: &mut KeybindManager, config: &BTreeMap<String,
         ("move_down", KeyAction::MoveDown),
         ("select", KeyAction::Select),
         ("delete_session", KeyAction::DeleteSession),
+        ("rename_session", KeyAction::RenameSession),
+        ("toggle_mark", KeyAction::ToggleMark),
+        ("toggle_pin", KeyAction::TogglePin),
+        ("cycle_sort_mode", KeyAction::CycleSortMode),
+        ("previous_session", KeyAction::PreviousSession),
+        ("kill_all_except_current", KeyAction::KillAllExceptCurrent),
+        ("prune_sessions", KeyAction::PruneSessions),
+        ("remove_missing_directories", KeyAction::RemoveMissingDirectories),
+        ("remove_from_zoxide", KeyAction::RemoveFromZoxide),
+        ("add_to_zoxide", KeyAction::AddToZoxide),
         ("exit", KeyAction::Exit),
         ("clear_search", KeyAction::ClearSearch),
         ("confirm", KeyAction::Confirm),
@@ -123,4 +327,70 @@ pub fn validate_keybind_config(keybinds: &KeybindManager) -> Vec<String> {
     }
     
     errors
-}
\ No newline at end of file
+}
+
+/// Parse a duration such as "30m", "12h", "7d" or "2w" (a bare number is treated as days)
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(idx) => value.split_at(idx),
+        None => (value, "d"),
+    };
+    
+    let amount: u64 = number
+        .parse()
+        .map_err(|_| format!("'{}' is not a valid duration", value))?;
+    
+    let unit_secs = match unit.trim() {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        other => return Err(format!("Unknown duration unit '{}' (expected s, m, h, d or w)", other)),
+    };
+    
+    let secs = amount
+        .checked_mul(unit_secs)
+        .ok_or_else(|| format!("'{}' is too long a duration", value))?;
+    Ok(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30m").unwrap(), Duration::from_secs(30 * 60));
+        assert_eq!(parse_duration("12h").unwrap(), Duration::from_secs(12 * 60 * 60));
+        assert_eq!(parse_duration("7d").unwrap(), Duration::from_secs(7 * 24 * 60 * 60));
+        assert_eq!(parse_duration("2w").unwrap(), Duration::from_secs(14 * 24 * 60 * 60));
+        assert_eq!(parse_duration(" 3 ").unwrap(), Duration::from_secs(3 * 24 * 60 * 60));
+        
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("7y").is_err());
+        assert!(parse_duration("18446744073709551615w").is_err());
+    }
+    
+    #[test]
+    fn test_parse_directory_sources() {
+        let config = BTreeMap::from([
+            ("glob_roots".to_string(), "~/code/*/* ~/work/*".to_string()),
+            ("project_roots".to_string(), "~/src".to_string()),
+            ("project_base_score".to_string(), "2.5".to_string()),
+            ("ghq".to_string(), "true".to_string()),
+            ("directory_command".to_string(), "fd -td . ~/src".to_string()),
+        ]);
+        
+        assert_eq!(parse_directory_sources(&config), vec![
+            DirectorySource::Zoxide,
+            DirectorySource::Glob(vec!["~/code/*/*".to_string(), "~/work/*".to_string()]),
+            DirectorySource::Projects { roots: vec!["~/src".to_string()], depth: 3, base_score: 2.5 },
+            DirectorySource::Ghq,
+            DirectorySource::Command("fd -td . ~/src".to_string()),
+        ]);
+        assert_eq!(parse_directory_sources(&BTreeMap::new()), vec![DirectorySource::Zoxide]);
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use crate::pins::Pin;
+use crate::storage;
+use crate::recency::recency_weight;
+
+/// File in the plugin data directory holding the selection history
+const HISTORY_FILE: &str = "history.tsv";
+
+/// Number of selections kept; older ones are forgotten
+const MAX_SELECTIONS: usize = 500;
+
+/// How many zoxide visits a selection through ZSM is worth
+const SELECTION_WEIGHT: f64 = 10.0;
+
+/// Upper bound of the bonus added to fuzzy match scores, so usage never outweighs match quality
+const MAX_SEARCH_BONUS: f64 = 50.0;
+
+/// A session (and its directory, when known) picked in ZSM
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selection {
+    /// Selection time in seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Name of the session switched to or created
+    pub session: String,
+    /// Directory of the session, when known
+    pub path: Option<String>,
+}
+
+/// Persistent history of the sessions and directories picked in ZSM, oldest first.
+/// Stored as one `timestamp<TAB>session<TAB>path` line per selection (empty path when unknown).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct UsageHistory {
+    selections: Vec<Selection>,
+}
+
+impl UsageHistory {
+    /// Load the history from the plugin data directory
+    pub fn load() -> Self {
+        Self::from_lines(&storage::read_lines(HISTORY_FILE))
+    }
+
+    /// Parse the history from stored lines, skipping malformed ones
+    pub fn from_lines(lines: &[String]) -> Self {
+        let selections = lines
+            .iter()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let timestamp = fields.next()?.parse().ok()?;
+                let session = fields.next().filter(|session| !session.is_empty())?;
+                let path = fields.next().filter(|path| !path.is_empty());
+                Some(Selection {
+                    timestamp,
+                    session: session.to_string(),
+                    path: path.map(|path| path.to_string()),
+                })
+            })
+            .collect();
+        Self { selections }
+    }
+
+    /// Serialize the history to lines
+    pub fn to_lines(&self) -> Vec<String> {
+        self.selections
+            .iter()
+            .map(|selection| {
+                format!(
+                    "{}\t{}\t{}",
+                    selection.timestamp,
+                    selection.session,
+                    selection.path.as_deref().unwrap_or("")
+                )
+            })
+            .collect()
+    }
+
+    /// Record a selection and save the history. The file is re-read first, since the plugin
+    /// instances of other Zellij sessions record their selections there too.
+    pub fn record(&mut self, session: &str, path: Option<&str>, timestamp: u64) {
+        *self = Self::load();
+        self.selections.push(Selection {
+            timestamp,
+            session: session.to_string(),
+            path: path.map(|path| path.to_string()),
+        });
+        let excess = self.selections.len().saturating_sub(MAX_SELECTIONS);
+        self.selections.drain(..excess);
+
+        if let Err(err) = storage::write_lines(HISTORY_FILE, &self.to_lines()) {
+            eprintln!("Warning: Failed to save usage history: {}", err);
+        }
+    }
+
+    /// Usage score of a directory: every selection counts as `SELECTION_WEIGHT` visits,
+    /// weighted by how recently it happened (like zoxide weights its ranks)
+    pub fn directory_score(&self, path: &str, now: u64) -> f64 {
+        self.selections
+            .iter()
+            .filter(|selection| selection.path.as_deref() == Some(path))
+            .map(|selection| SELECTION_WEIGHT * recency_weight(selection.timestamp, now))
+            .sum()
+    }
+
+    /// Time a directory was last selected
+    pub fn last_selected(&self, path: &str) -> Option<u64> {
+        self.selections
+            .iter()
+            .filter(|selection| selection.path.as_deref() == Some(path))
+            .map(|selection| selection.timestamp)
+            .max()
+    }
+
+    /// Bonus added to the fuzzy match score of each selected session and directory
+    pub fn search_bonuses(&self, now: u64) -> HashMap<Pin, i64> {
+        let mut scores: HashMap<Pin, f64> = HashMap::new();
+        for selection in &self.selections {
+            let score = SELECTION_WEIGHT * recency_weight(selection.timestamp, now);
+            *scores.entry(Pin::Session(selection.session.clone())).or_default() += score;
+            if let Some(path) = &selection.path {
+                *scores.entry(Pin::Directory(path.clone())).or_default() += score;
+            }
+        }
+        scores
+            .into_iter()
+            .map(|(item, score)| (item, score.min(MAX_SEARCH_BONUS).round() as i64))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    #[test]
+    fn test_history_round_trip() {
+        let lines = vec![
+            format!("{}\twork\t/home/user/work", NOW - 60),
+            format!("{}\tscratch\t", NOW - 2 * 60 * 60),
+            "not a timestamp\twork\t/home/user/work".to_string(),
+        ];
+        let history = UsageHistory::from_lines(&lines);
+
+        assert_eq!(history.selections.len(), 2);
+        assert_eq!(history.selections[1].path, None);
+        assert_eq!(UsageHistory::from_lines(&history.to_lines()), history);
+    }
+
+    #[test]
+    fn test_usage_scores() {
+        let history = UsageHistory::from_lines(&[
+            format!("{}\twork\t/home/user/work", NOW - 30 * 24 * 60 * 60),
+            format!("{}\twork\t/home/user/work", NOW - 60),
+            format!("{}\tscratch\t", NOW - 2 * 60 * 60),
+        ]);
+
+        assert_eq!(history.directory_score("/home/user/work", NOW), 10.0 * 0.25 + 10.0 * 4.0);
+        assert_eq!(history.directory_score("/elsewhere", NOW), 0.0);
+        assert_eq!(history.last_selected("/home/user/work"), Some(NOW - 60));
+
+        let bonuses = history.search_bonuses(NOW);
+        assert_eq!(bonuses.get(&Pin::Directory("/home/user/work".to_string())), Some(&43));
+        assert_eq!(bonuses.get(&Pin::Session("scratch".to_string())), Some(&20));
+    }
+}
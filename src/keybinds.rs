@@ -9,6 +9,16 @@ pub enum KeyAction {
     MoveDown,
     Select,
     DeleteSession,
+    RenameSession,
+    ToggleMark,
+    TogglePin,
+    CycleSortMode,
+    PreviousSession,
+    KillAllExceptCurrent,
+    PruneSessions,
+    RemoveMissingDirectories,
+    RemoveFromZoxide,
+    AddToZoxide,
     Exit,
     ClearSearch,
     
@@ -54,6 +64,16 @@ impl KeybindManager {
         self.add_binding(KeyAction::MoveDown, key_with_ctrl('n'));
         self.add_binding(KeyAction::Select, key_from_bare(BareKey::Enter));
         self.add_binding(KeyAction::DeleteSession, key_from_bare(BareKey::Delete));
+        self.add_binding(KeyAction::RenameSession, key_from_bare(BareKey::F(2)));
+        self.add_binding(KeyAction::ToggleMark, key_from_bare(BareKey::Tab));
+        self.add_binding(KeyAction::TogglePin, key_with_ctrl('b'));
+        self.add_binding(KeyAction::CycleSortMode, key_with_ctrl('s'));
+        self.add_binding(KeyAction::PreviousSession, key_with_alt('p'));
+        self.add_binding(KeyAction::KillAllExceptCurrent, key_with_ctrl('x'));
+        self.add_binding(KeyAction::PruneSessions, key_with_ctrl('d'));
+        self.add_binding(KeyAction::RemoveMissingDirectories, key_with_alt('d'));
+        self.add_binding(KeyAction::RemoveFromZoxide, key_with_alt('r'));
+        self.add_binding(KeyAction::AddToZoxide, key_with_alt('a'));
         self.add_binding(KeyAction::ClearSearch, key_from_bare(BareKey::Esc));
         self.add_binding(KeyAction::Exit, key_with_ctrl('c'));
         self.add_binding(KeyAction::Backspace, key_from_bare(BareKey::Backspace));
@@ -155,6 +175,16 @@ fn key_with_ctrl(c: char) -> KeyWithModifier {
     }
 }
 
+/// Helper function to create a KeyWithModifier with Alt modifier
+fn key_with_alt(c: char) -> KeyWithModifier {
+    let mut modifiers = BTreeSet::new();
+    modifiers.insert(KeyModifier::Alt);
+    KeyWithModifier {
+        bare_key: BareKey::Char(c),
+        key_modifiers: modifiers,
+    }
+}
+
 /// Format a key combination for display
 pub fn format_key_for_display(key: &KeyWithModifier) -> String {
     let mut parts = Vec::new();
@@ -315,6 +345,26 @@ mod tests {
             manager.get_action(&key_from_bare(BareKey::Delete)),
             Some(KeyAction::DeleteSession)
         );
+        assert_eq!(
+            manager.get_action(&key_from_bare(BareKey::Tab)),
+            Some(KeyAction::ToggleMark)
+        );
+        assert_eq!(
+            manager.get_action(&key_with_ctrl('x')),
+            Some(KeyAction::KillAllExceptCurrent)
+        );
+        assert_eq!(
+            manager.get_action(&key_with_ctrl('b')),
+            Some(KeyAction::TogglePin)
+        );
+        assert_eq!(
+            manager.get_action(&key_with_ctrl('s')),
+            Some(KeyAction::CycleSortMode)
+        );
+        assert_eq!(
+            manager.get_action(&key_with_alt('p')),
+            Some(KeyAction::PreviousSession)
+        );
     }
     
     #[test]
@@ -323,7 +373,7 @@ mod tests {
         
         let key = KeyWithModifier {
             bare_key: BareKey::Char('a'),
-            modifiers: vec![],
+            key_modifiers: BTreeSet::new(),
         };
         
         assert_eq!(
@@ -387,8 +437,8 @@ mod tests {
         // Test multiple modifiers
         let key = parse_key_string("Ctrl+Alt+a").unwrap();
         assert_eq!(key.bare_key, BareKey::Char('a'));
-        assert!(key.modifiers.contains(&KeyModifier::Ctrl));
-        assert!(key.modifiers.contains(&KeyModifier::Alt));
+        assert!(key.key_modifiers.contains(&KeyModifier::Ctrl));
+        assert!(key.key_modifiers.contains(&KeyModifier::Alt));
         
         // Test invalid keys
         assert!(parse_key_string("").is_err());
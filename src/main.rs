@@ -1,8 +1,12 @@
 mod config;
+mod history;
 mod keybinds;
 mod new_session_info;
+mod pins;
+mod recency;
 mod session;
 mod state;
+mod storage;
 mod ui;
 mod zoxide;
 
@@ -31,7 +35,9 @@ impl ZellijPlugin for PluginState {
             EventType::SessionUpdate,
             EventType::Key,
             EventType::RunCommandResult,
-            EventType::PermissionRequestResult
+            EventType::PermissionRequestResult,
+            EventType::Timer,
+            EventType::Visible,
         ]);
 
         // Don't fetch zoxide directories immediately - wait for permissions
@@ -52,31 +58,125 @@ impl ZellijPlugin for PluginState {
             Event::PermissionRequestResult(permission_status) => {
                 match permission_status {
                     PermissionStatus::Granted => {
-                        // Now that we have permissions, fetch zoxide directories
-                        self.fetch_zoxide_directories();
+                        // Now that we have permissions, fetch directories and session layouts
+                        self.set_permissions_granted();
+                        self.config().zoxide.check_version();
+                        self.fetch_directories();
+                        self.schedule_refresh();
                         should_render = true;
                     }
                     PermissionStatus::Denied => {
-                        self.set_error("RunCommands permission denied - cannot fetch zoxide directories".to_string());
+                        // Without RunCommands, read zoxide's database through the host folder
+                        self.set_database_fallback();
+                        self.fetch_directories();
+                        self.schedule_refresh();
                         should_render = true;
                     }
                 }
             }
-            Event::SessionUpdate(session_infos, _) => {
-                self.update_sessions(session_infos);
+            Event::Timer(_) => {
+                // Periodic refresh; results come back as RunCommandResult events.
+                // Session directories are only inferred again here, as that dumps every layout.
+                should_render = self.fetch_directories();
+                self.expire_session_directories();
+                self.schedule_refresh();
+            }
+            Event::Visible(visible) => {
+                if visible && (self.permissions_granted() || self.database_fallback()) {
+                    should_render = self.fetch_directories();
+                }
+            }
+            Event::SessionUpdate(session_infos, resurrectable_sessions) => {
+                self.update_sessions(session_infos, resurrectable_sessions);
                 should_render = true;
             }
             Event::RunCommandResult(exit_code, stdout, stderr, context) => {
-                if context.contains_key("zoxide_query") {
+                if let Some(index) = context.get("directory_source").and_then(|i| i.parse::<usize>().ok()) {
+                    if exit_code == Some(0) {
+                        let stdout_str = String::from_utf8_lossy(&stdout);
+                        should_render = self.process_source_output(index, &stdout_str);
+                        self.fetch_worktrees();
+                        self.check_missing_directories();
+                    } else {
+                        let stderr_str = String::from_utf8_lossy(&stderr);
+                        match self.config().directory_sources.get(index) {
+                            Some(zoxide::DirectorySource::Zoxide) => {
+                                let error = zoxide::ZoxideError::from_failure(exit_code, &stderr_str);
+                                self.apply_zoxide_error(index, error);
+                                should_render = true;
+                            }
+                            // Other sources are optional: keep their last listing and only warn
+                            Some(source) => {
+                                eprintln!("Warning: Failed to list {} directories: {}", source.tag(), stderr_str.trim());
+                            }
+                            None => return false,
+                        }
+                    }
+                } else if let Some(session_name) = context.get("session_layout") {
+                    let stdout_str = String::from_utf8_lossy(&stdout);
+                    let layout = if exit_code == Some(0) { Some(stdout_str.as_ref()) } else { None };
+                    self.process_session_layout(session_name, layout);
+                    should_render = true;
+                } else if context.contains_key("worktree_list") {
                     if exit_code == Some(0) {
                         let stdout_str = String::from_utf8_lossy(&stdout);
-                        self.process_zoxide_output(&stdout_str);
+                        self.process_worktree_output(&stdout_str);
                         should_render = true;
+                    }
+                } else if let Some(path) = context.get("directory_preview") {
+                    let stdout_str = String::from_utf8_lossy(&stdout);
+                    let output = if exit_code == Some(0) { Some(stdout_str.as_ref()) } else { None };
+                    self.process_directory_preview(path, output);
+                    should_render = true;
+                } else if context.contains_key("zoxide_version") {
+                    // Failures to run zoxide at all are reported by the directory query
+                    if exit_code == Some(0) {
+                        let stdout_str = String::from_utf8_lossy(&stdout);
+                        if let Err(error) = zoxide::command::check_version_output(&stdout_str) {
+                            self.report_zoxide_error(error);
+                            should_render = true;
+                        }
+                    }
+                } else if context.contains_key("missing_directories") {
+                    if exit_code == Some(0) {
+                        let stdout_str = String::from_utf8_lossy(&stdout);
+                        self.update_missing_directories(zoxide::missing::parse_missing_output(&stdout_str));
+                        should_render = true;
+                    }
+                } else if let Some(subcommand) = context.get("zoxide_update") {
+                    if exit_code == Some(0) {
+                        // Reload the list to pick up the removed entries or new scores
+                        self.fetch_directories();
                     } else {
                         let stderr_str = String::from_utf8_lossy(&stderr);
-                        self.set_error(format!("Failed to run zoxide (is it installed?): {}", stderr_str));
+                        self.set_error(format!("Failed to run zoxide {}: {}", subcommand, stderr_str.trim()));
                         should_render = true;
                     }
+                } else if context.contains_key("home_directory") {
+                    let stdout_str = String::from_utf8_lossy(&stdout);
+                    if exit_code == Some(0) && !stdout_str.trim().is_empty() {
+                        // Directories listed so far were filtered without `~/` patterns
+                        self.set_home_directory(stdout_str.trim());
+                        should_render = self.fetch_directories();
+                    } else {
+                        eprintln!("Warning: Failed to read the home directory: {}", String::from_utf8_lossy(&stderr).trim());
+                    }
+                } else if let Some(path) = context.get("zoxide_visit") {
+                    // Visits are recorded while switching away, so a failure is only logged
+                    if exit_code != Some(0) {
+                        let stderr_str = String::from_utf8_lossy(&stderr);
+                        eprintln!("Warning: Failed to record zoxide visit to {}: {}", path, stderr_str.trim());
+                    }
+                } else if let Some(session_name) = context.get("session_rename") {
+                    if exit_code == Some(0) {
+                        if let Some(new_name) = context.get("session_rename_to") {
+                            self.apply_session_rename(session_name, new_name);
+                        }
+                    } else {
+                        let stderr_str = String::from_utf8_lossy(&stderr);
+                        self.set_error(format!("Failed to rename session '{}': {}", session_name, stderr_str.trim()));
+                    }
+                    should_render = true;
                 }
             }
             _ => (),
@@ -133,45 +233,133 @@ impl ZellijPlugin for PluginState {
 }
 
 impl PluginState {
-    fn fetch_zoxide_directories(&mut self) {
+    /// Fetch all directory sources, or read zoxide's database when commands can't be run.
+    /// Returns whether the list changed right away (command results arrive later).
+    fn fetch_directories(&mut self) -> bool {
+        self.expire_previews();
+        if self.database_fallback() {
+            return self.read_zoxide_database();
+        }
+        let config = self.config();
+        for (index, source) in config.directory_sources.iter().enumerate() {
+            source.fetch(index, &config.zoxide);
+        }
+        false
+    }
+
+    /// Populate the zoxide source from its database file
+    fn read_zoxide_database(&mut self) -> bool {
+        let config = self.config();
+        let Some(index) = config.directory_sources.iter().position(|s| *s == zoxide::DirectorySource::Zoxide) else {
+            return false;
+        };
+        match self.zoxide_database_entries() {
+            Ok(entries) => {
+                let directories = zoxide::DirectorySource::Zoxide.entry_directories(entries);
+                self.apply_source_directories(index, directories, None)
+            }
+            Err(error) => self.apply_zoxide_error(index, error),
+        }
+    }
+
+    fn schedule_refresh(&mut self) {
+        let interval = self.config().refresh_interval;
+        if !interval.is_zero() {
+            set_timeout(interval.as_secs_f64());
+        }
+    }
+
+    fn fetch_worktrees(&mut self) {
+        let directories = self.zoxide_directories();
+        if directories.is_empty() {
+            return;
+        }
+        
+        let mut command = vec!["sh", "-c", zoxide::worktree::WORKTREE_SCRIPT, "sh"];
+        command.extend(directories.iter().map(|d| d.directory.as_str()));
+        
         let mut context = BTreeMap::new();
-        context.insert("zoxide_query".to_string(), "true".to_string());
-        run_command(
-            &["zoxide", "query", "-l", "-s"],
-            context,
-        );
+        context.insert("worktree_list".to_string(), "true".to_string());
+        run_command(&command, context);
     }
 
-    fn process_zoxide_output(&mut self, output: &str) {
-        let mut directories = Vec::new();
+    fn check_missing_directories(&mut self) {
+        let paths: Vec<&str> = self.zoxide_directories().iter().map(|d| d.directory.as_str()).collect();
+        zoxide::missing::request_missing_check(&paths);
+    }
+
+    fn process_worktree_output(&mut self, output: &str) {
+        self.update_worktrees(zoxide::worktree::parse_worktree_output(output));
+        self.update_git_checkouts(zoxide::worktree::parse_checkout_output(output));
         
-        for line in output.lines() {
-            if line.trim().is_empty() {
-                continue;
+        // Re-generate names now that worktree branches are known
+        let mut directories = self.zoxide_directories().to_vec();
+        self.generate_smart_session_names(&mut directories);
+        self.update_zoxide_directories(directories);
+    }
+
+    /// Merge a source's output into the directory list, returning whether the list changed
+    fn process_source_output(&mut self, index: usize, output: &str) -> bool {
+        let Some(source) = self.config().directory_sources.get(index) else {
+            return false;
+        };
+        let (mut directories, problem) = source.parse_output(output);
+        if *source == zoxide::DirectorySource::Zoxide {
+            self.fill_from_database(&mut directories);
+        }
+        self.apply_source_directories(index, directories, problem)
+    }
+
+    /// Fill in the raw ranks and access times missing from zoxide's output from its database,
+    /// when readable
+    fn fill_from_database(&mut self, directories: &mut [zoxide::ZoxideDirectory]) {
+        let Ok(entries) = self.zoxide_database_entries() else {
+            return;
+        };
+        let entries: std::collections::HashMap<&str, &zoxide::ZoxideEntry> =
+            entries.iter().map(|entry| (entry.path.as_str(), entry)).collect();
+        for directory in directories.iter_mut() {
+            if let Some(entry) = entries.get(directory.directory.as_str()) {
+                directory.rank = directory.rank.or(entry.rank);
+                directory.last_accessed = directory.last_accessed.or(entry.last_accessed);
             }
-            
-            // zoxide output format: "score path"
-            let parts: Vec<&str> = line.trim().splitn(2, ' ').collect();
-            if parts.len() == 2 {
-                if let Ok(score) = parts[0].parse::<f64>() {
-                    let path = parts[1];
-                    
-                    directories.push(zoxide::ZoxideDirectory {
-                        ranking: score,
-                        directory: path.to_string(),
-                        session_name: String::new(), // Will be set by smart naming
-                    });
-                }
+        }
+    }
+
+    /// Report a failed zoxide listing, returning whether the list changed. An empty database
+    /// still replaces the previously listed zoxide directories.
+    fn apply_zoxide_error(&mut self, index: usize, error: zoxide::ZoxideError) -> bool {
+        if error == zoxide::ZoxideError::EmptyDatabase {
+            return self.apply_source_directories(index, Vec::new(), Some(error));
+        }
+        self.report_zoxide_error(error);
+        true
+    }
+
+    /// Merge a source's parsed directories into the list, returning whether the list changed.
+    /// For zoxide, a problem found while parsing is reported (and a previous one forgotten).
+    fn apply_source_directories(
+        &mut self,
+        index: usize,
+        directories: Vec<zoxide::ZoxideDirectory>,
+        problem: Option<zoxide::ZoxideError>,
+    ) -> bool {
+        if self.config().directory_sources.get(index) == Some(&zoxide::DirectorySource::Zoxide) {
+            match problem {
+                Some(error) => self.report_zoxide_error(error),
+                None => self.clear_zoxide_error(),
             }
         }
+        let mut directories = self.update_source_directories(index, directories);
         
         // Generate smart session names before sorting
         self.generate_smart_session_names(&mut directories);
         
-        // Sort by score in descending order (higher scores first)
-        directories.sort_by(|a, b| b.ranking.partial_cmp(&a.ranking).unwrap_or(std::cmp::Ordering::Equal));
+        // Sort in the current mode (by score unless switched), blending in the usage history;
+        // the sort is stable, so unranked directories keep their source order
+        self.sort_directories(&mut directories);
         
-        self.update_zoxide_directories(directories);
+        self.update_zoxide_directories(directories)
     }
 
     fn generate_smart_session_names(&self, directories: &mut Vec<zoxide::ZoxideDirectory>) {
@@ -212,6 +400,46 @@ impl PluginState {
                 }
             }
         }
+        
+        // Linked git worktrees are named after their repository and checked-out branch
+        for dir in directories.iter_mut() {
+            if let Some(name) = self.worktree_session_name(&dir.directory) {
+                dir.session_name = name;
+            }
+        }
+        
+        // A configured naming template overrides the smart names wherever it can be rendered,
+        // falling back on them to tell directories rendering the same name apart
+        if let zoxide::NamingStrategy::Template(template) = &self.config().naming_strategy {
+            let separator = &self.config().session_separator;
+            let (indices, mut names): (Vec<usize>, Vec<zoxide::naming::RenderedName>) = directories
+                .iter()
+                .enumerate()
+                .filter_map(|(i, dir)| {
+                    let name = zoxide::naming::template_session_name(
+                        template,
+                        &dir.directory,
+                        self.home_directory(),
+                        self.git_checkout(&dir.directory),
+                        separator,
+                    )?;
+                    Some((i, zoxide::naming::RenderedName { path: &dir.directory, name, smart_name: &dir.session_name }))
+                })
+                .unzip();
+            zoxide::naming::resolve_conflicts(&mut names, separator);
+            let names: Vec<String> = names.into_iter().map(|rendered| rendered.name).collect();
+            for (i, name) in indices.into_iter().zip(names) {
+                directories[i].session_name = name;
+            }
+        }
+    }
+    
+    fn worktree_session_name(&self, path: &str) -> Option<String> {
+        let worktree = self.worktree(path)?;
+        let branch = worktree.branch.as_ref()?;
+        let separator = &self.config().session_separator;
+        // Branches like "feature/login" can't be used as-is since session names can't contain '/'
+        Some(format!("{}{}{}", worktree.repository_name(), separator, branch.replace('/', "-")))
     }
     
     fn is_nested_in_zoxide_directories(&self, path: &str, all_directories: &[zoxide::ZoxideDirectory]) -> bool {
@@ -309,12 +537,7 @@ impl PluginState {
     
     fn normalize_path(&self, path: &str) -> String {
         // Remove home directory prefix if present
-        if let Some(home) = dirs::home_dir() {
-            if let Ok(relative) = std::path::Path::new(path).strip_prefix(&home) {
-                return relative.to_string_lossy().to_string();
-            }
-        }
-        path.to_string()
+        zoxide::naming::relative_to_home(path, self.home_directory()).to_string()
     }
     
     fn apply_smart_truncation(&self, segments: &[&str], min_segments: usize) -> String {
@@ -391,4 +614,4 @@ impl PluginState {
         
         segment.to_string()
     }
-}
\ No newline at end of file
+}
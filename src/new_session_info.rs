@@ -145,14 +145,18 @@ impl NewSessionInfo {
         }
     }
     
-    pub fn handle_quick_session_creation(&mut self, current_session_name: &Option<String>, default_layout: &Option<String>) {
+    /// Create the session immediately with the default layout.
+    /// Returns the name and folder of the created session when both are known.
+    pub fn handle_quick_session_creation(&mut self, current_session_name: &Option<String>, default_layout: &Option<String>) -> Option<(String, PathBuf)> {
         let new_session_name = if self.name.is_empty() {
             None
         } else {
             Some(self.name.as_str())
         };
+        let mut created_session = None;
         
         if new_session_name != current_session_name.as_ref().map(|s| s.as_str()) {
+            created_session = self.created_session();
             match default_layout {
                 Some(layout_name) => {
                     // Find the layout by name
@@ -183,9 +187,13 @@ impl NewSessionInfo {
         self.name.clear();
         self.layout_list.clear_selection();
         hide_self();
+        created_session
     }
     
-    pub fn handle_selection(&mut self, current_session_name: &Option<String>) {
+    /// Advance through the new session screen, creating the session once a layout is chosen.
+    /// Returns the name and folder of the created session when both are known.
+    pub fn handle_selection(&mut self, current_session_name: &Option<String>) -> Option<(String, PathBuf)> {
+        let mut created_session = None;
         match self.entering_new_session_info {
             EnteringState::EnteringLayoutSearch => {
                 let new_session_layout: Option<LayoutInfo> = self.selected_layout_info();
@@ -195,6 +203,7 @@ impl NewSessionInfo {
                     Some(self.name.as_str())
                 };
                 if new_session_name != current_session_name.as_ref().map(|s| s.as_str()) {
+                    created_session = self.created_session();
                     match new_session_layout {
                         Some(new_session_layout) => {
                             let cwd = self.new_session_folder.as_ref().map(|c| PathBuf::from(c));
@@ -214,6 +223,17 @@ impl NewSessionInfo {
                 self.entering_new_session_info = EnteringState::EnteringLayoutSearch;
             },
         }
+        created_session
+    }
+    
+    /// Name and folder of the session about to be created (unknown for random names)
+    fn created_session(&self) -> Option<(String, PathBuf)> {
+        if self.name.is_empty() {
+            return None;
+        }
+        self.new_session_folder
+            .as_ref()
+            .map(|folder| (self.name.clone(), folder.clone()))
     }
     pub fn update_layout_list(&mut self, layout_info: Vec<LayoutInfo>) {
         self.layout_list.update_layout_list(layout_info);
@@ -0,0 +1,156 @@
+use crate::session::SessionItem;
+use crate::storage;
+
+/// File in the plugin data directory holding the pinned items
+const PINS_FILE: &str = "pins.tsv";
+
+/// A pinned session or directory
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Pin {
+    /// A session, pinned by name (live or exited)
+    Session(String),
+    /// A directory, pinned by path
+    Directory(String),
+}
+
+impl Pin {
+    /// Get the pin identifying a list item
+    pub fn for_item(item: &SessionItem) -> Self {
+        match item {
+            SessionItem::ExistingSession { name, .. } | SessionItem::ResurrectableSession { name, .. } => {
+                Pin::Session(name.clone())
+            }
+            SessionItem::Directory { path, .. } => Pin::Directory(path.clone()),
+        }
+    }
+}
+
+/// Persistent list of pinned sessions and directories, in the order they were pinned.
+/// Stored as one `session<TAB>name` or `directory<TAB>path` pair per line.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PinnedItems {
+    pins: Vec<Pin>,
+}
+
+impl PinnedItems {
+    /// Load the pins from the plugin data directory
+    pub fn load() -> Self {
+        Self::from_lines(&storage::read_lines(PINS_FILE))
+    }
+
+    /// Parse the pins from stored lines, skipping malformed ones
+    pub fn from_lines(lines: &[String]) -> Self {
+        let mut pinned = Self::default();
+        for line in lines {
+            let pin = match line.split_once('\t') {
+                Some(("session", name)) if !name.is_empty() => Pin::Session(name.to_string()),
+                Some(("directory", path)) if !path.is_empty() => Pin::Directory(path.to_string()),
+                _ => continue,
+            };
+            if !pinned.pins.contains(&pin) {
+                pinned.pins.push(pin);
+            }
+        }
+        pinned
+    }
+
+    /// Serialize the pins to lines
+    pub fn to_lines(&self) -> Vec<String> {
+        self.pins
+            .iter()
+            .map(|pin| match pin {
+                Pin::Session(name) => format!("session\t{}", name),
+                Pin::Directory(path) => format!("directory\t{}", path),
+            })
+            .collect()
+    }
+
+    /// Write the pins to the plugin data directory
+    fn save(&self) {
+        if let Err(err) = storage::write_lines(PINS_FILE, &self.to_lines()) {
+            eprintln!("Warning: Failed to save pins: {}", err);
+        }
+    }
+
+    /// Pin or unpin an item, returning whether it is now pinned
+    pub fn toggle(&mut self, pin: Pin) -> bool {
+        let pinned = match self.pins.iter().position(|p| *p == pin) {
+            Some(index) => {
+                self.pins.remove(index);
+                false
+            }
+            None => {
+                self.pins.push(pin);
+                true
+            }
+        };
+        self.save();
+        pinned
+    }
+
+    /// Keep a session pinned after it was renamed
+    pub fn rename_session(&mut self, from: &str, to: &str) {
+        let renamed = Pin::Session(to.to_string());
+        if let Some(pin) = self.pins.iter_mut().find(|p| **p == Pin::Session(from.to_string())) {
+            *pin = renamed;
+            self.save();
+        }
+    }
+
+    /// Position of an item in the pinned section (None when not pinned)
+    pub fn position(&self, item: &SessionItem) -> Option<usize> {
+        let pin = Pin::for_item(item);
+        self.pins.iter().position(|p| *p == pin)
+    }
+
+    /// Check if an item is pinned
+    pub fn is_pinned(&self, item: &SessionItem) -> bool {
+        self.position(item).is_some()
+    }
+
+    /// Get all pins, in the order they were pinned
+    pub fn pins(&self) -> &[Pin] {
+        &self.pins
+    }
+
+    /// Get the pinned directory paths
+    pub fn directories(&self) -> impl Iterator<Item = &str> {
+        self.pins.iter().filter_map(|pin| match pin {
+            Pin::Directory(path) => Some(path.as_str()),
+            Pin::Session(_) => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pins_round_trip() {
+        let lines = vec![
+            "directory\t/home/user/dotfiles".to_string(),
+            "session\twork".to_string(),
+            "session\twork".to_string(),
+            "bogus line".to_string(),
+        ];
+        let pinned = PinnedItems::from_lines(&lines);
+
+        assert_eq!(pinned.pins(), &[
+            Pin::Directory("/home/user/dotfiles".to_string()),
+            Pin::Session("work".to_string()),
+        ]);
+        assert_eq!(PinnedItems::from_lines(&pinned.to_lines()), pinned);
+        assert_eq!(pinned.directories().collect::<Vec<_>>(), vec!["/home/user/dotfiles"]);
+    }
+
+    #[test]
+    fn test_pin_position_matches_items() {
+        let pinned = PinnedItems::from_lines(&["session\twork".to_string()]);
+        let live = SessionItem::ExistingSession { name: "work".to_string(), directory: None, is_current: false };
+        let other = SessionItem::ExistingSession { name: "play".to_string(), directory: None, is_current: false };
+
+        assert_eq!(pinned.position(&live), Some(0));
+        assert!(!pinned.is_pinned(&other));
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use crate::storage;
+
+/// File in the plugin data directory holding the session to directory mapping
+const DIRECTORY_MAP_FILE: &str = "session_directories.tsv";
+
+/// Persistent record of which directory each session was created from.
+/// Stored as one `session<TAB>directory` pair per line.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SessionDirectoryMap {
+    /// Session name to directory path
+    entries: BTreeMap<String, String>,
+}
+
+impl SessionDirectoryMap {
+    /// Load the mapping from the plugin data directory
+    pub fn load() -> Self {
+        Self::from_lines(&storage::read_lines(DIRECTORY_MAP_FILE))
+    }
+
+    /// Parse the mapping from stored lines, skipping malformed ones
+    pub fn from_lines(lines: &[String]) -> Self {
+        let entries = lines
+            .iter()
+            .filter_map(|line| line.split_once('\t'))
+            .filter(|(session, directory)| !session.is_empty() && !directory.is_empty())
+            .map(|(session, directory)| (session.to_string(), directory.to_string()))
+            .collect();
+        Self { entries }
+    }
+
+    /// Serialize the mapping to lines
+    pub fn to_lines(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .map(|(session, directory)| format!("{}\t{}", session, directory))
+            .collect()
+    }
+
+    /// Write the mapping to the plugin data directory
+    fn save(&self) {
+        if let Err(err) = storage::write_lines(DIRECTORY_MAP_FILE, &self.to_lines()) {
+            eprintln!("Warning: Failed to save session directories: {}", err);
+        }
+    }
+
+    /// Record the directory a session was created from
+    pub fn insert(&mut self, session_name: &str, directory: &str) {
+        if self.entries.get(session_name).map(|d| d.as_str()) != Some(directory) {
+            self.entries.insert(session_name.to_string(), directory.to_string());
+            self.save();
+        }
+    }
+
+    /// Move a mapping to a session's new name
+    pub fn rename(&mut self, from: &str, to: &str) {
+        if let Some(directory) = self.entries.remove(from) {
+            self.entries.insert(to.to_string(), directory);
+            self.save();
+        }
+    }
+
+    /// Forget the directory of a session that no longer exists
+    pub fn remove(&mut self, session_name: &str) {
+        if self.entries.remove(session_name).is_some() {
+            self.save();
+        }
+    }
+
+    /// Get the directory a session was created from
+    pub fn directory_for(&self, session_name: &str) -> Option<&str> {
+        self.entries.get(session_name).map(|d| d.as_str())
+    }
+
+    /// Get all sessions created from a directory
+    pub fn sessions_for(&self, directory: &str) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(_, d)| d.as_str() == directory)
+            .map(|(session, _)| session.as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let lines = vec![
+            "app\t/home/user/projects/app".to_string(),
+            "app.2\t/home/user/projects/app".to_string(),
+            "malformed line".to_string(),
+            "\t/no/session".to_string(),
+        ];
+        let map = SessionDirectoryMap::from_lines(&lines);
+
+        assert_eq!(map.directory_for("app"), Some("/home/user/projects/app"));
+        assert_eq!(map.sessions_for("/home/user/projects/app"), vec!["app", "app.2"]);
+        assert_eq!(map.directory_for("malformed line"), None);
+        assert_eq!(map.to_lines(), lines[..2].to_vec());
+    }
+}
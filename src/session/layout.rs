@@ -0,0 +1,150 @@
+/// Infer a session's working directory from its serialized layout, as printed by
+/// `zellij action dump-layout`. Returns the most common pane cwd (resolving relative
+/// paths against the tab and layout cwd), falling back to the layout cwd.
+pub fn infer_directory_from_layout(layout: &str) -> Option<String> {
+    let mut layout_cwd: Option<String> = None;
+    let mut tab_cwd: Option<String> = None;
+    let mut pane_cwds: Vec<(String, usize)> = Vec::new();
+
+    for line in layout.lines() {
+        let line = line.trim();
+
+        // Templates and swap layouts describe panes that don't exist yet
+        if line.starts_with("new_tab_template") || line.starts_with("swap_") {
+            break;
+        }
+
+        if layout_cwd.is_none() && line.starts_with("cwd ") {
+            layout_cwd = quoted_value(&line[4..]);
+        } else if line.starts_with("tab ") || line == "tab" {
+            tab_cwd = attribute_value(line, "cwd");
+        } else if line.starts_with("pane") {
+            if let Some(cwd) = attribute_value(line, "cwd") {
+                let resolved = resolve_path(&cwd, tab_cwd.as_deref(), layout_cwd.as_deref());
+                match pane_cwds.iter_mut().find(|(path, _)| *path == resolved) {
+                    Some((_, count)) => *count += 1,
+                    None => pane_cwds.push((resolved, 1)),
+                }
+            }
+        }
+    }
+
+    // Most common pane cwd, ties going to the first one seen
+    let mut best: Option<&(String, usize)> = None;
+    for entry in &pane_cwds {
+        if best.is_none_or(|(_, count)| entry.1 > *count) {
+            best = Some(entry);
+        }
+    }
+
+    best.map(|(path, _)| path.clone())
+        .or_else(|| tab_cwd.filter(|cwd| cwd.starts_with('/')))
+        .or(layout_cwd)
+}
+
+/// Resolve a possibly relative pane cwd against the tab and layout cwds
+fn resolve_path(cwd: &str, tab_cwd: Option<&str>, layout_cwd: Option<&str>) -> String {
+    if cwd.starts_with('/') {
+        return cwd.to_string();
+    }
+
+    let base = match (tab_cwd, layout_cwd) {
+        (Some(tab), _) if tab.starts_with('/') => Some(tab.to_string()),
+        (Some(tab), Some(layout)) => Some(join_path(layout, tab)),
+        (None, Some(layout)) => Some(layout.to_string()),
+        _ => None,
+    };
+
+    match base {
+        Some(base) => join_path(&base, cwd),
+        None => cwd.to_string(),
+    }
+}
+
+fn join_path(base: &str, relative: &str) -> String {
+    format!("{}/{}", base.trim_end_matches('/'), relative.trim_start_matches("./"))
+}
+
+/// Extract the value of a `key="value"` attribute from a KDL node line
+fn attribute_value(line: &str, key: &str) -> Option<String> {
+    let pattern = format!("{}=", key);
+    let mut search_from = 0;
+    while let Some(idx) = line[search_from..].find(&pattern) {
+        let start = search_from + idx;
+        // Make sure we matched a whole attribute name (e.g. not "some_cwd=")
+        let preceded_by_space = start == 0 || line[..start].ends_with(' ');
+        if preceded_by_space {
+            return quoted_value(&line[start + pattern.len()..]);
+        }
+        search_from = start + pattern.len();
+    }
+    None
+}
+
+/// Read a KDL quoted string at the start of the input
+fn quoted_value(input: &str) -> Option<String> {
+    let mut chars = input.trim_start().chars();
+    if chars.next() != Some('"') {
+        return None;
+    }
+
+    let mut value = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(value),
+            '\\' => value.push(chars.next()?),
+            _ => value.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_most_common_pane_cwd() {
+        let layout = r#"
+layout {
+    cwd "/home/user"
+    tab name="code" focus=true {
+        pane size=1 borderless=true {
+            plugin location="zellij:tab-bar"
+        }
+        pane command="nvim" cwd="projects/app" {
+            args "."
+        }
+        pane cwd="projects/app"
+        pane cwd="/tmp"
+    }
+    new_tab_template {
+        pane cwd="/elsewhere"
+        pane cwd="/elsewhere"
+        pane cwd="/elsewhere"
+    }
+}
+"#;
+        assert_eq!(infer_directory_from_layout(layout), Some("/home/user/projects/app".to_string()));
+    }
+
+    #[test]
+    fn test_infer_falls_back_to_layout_cwd() {
+        let layout = "layout {\n    cwd \"/srv/api\"\n    tab {\n        pane\n    }\n}\n";
+        assert_eq!(infer_directory_from_layout(layout), Some("/srv/api".to_string()));
+        assert_eq!(infer_directory_from_layout("layout {\n}\n"), None);
+    }
+
+    #[test]
+    fn test_infer_resolves_against_tab_cwd() {
+        let layout = r#"
+layout {
+    cwd "/home/user"
+    tab cwd="work" {
+        pane cwd="api"
+    }
+}
+"#;
+        assert_eq!(infer_directory_from_layout(layout), Some("/home/user/work/api".to_string()));
+    }
+}
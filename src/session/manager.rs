@@ -1,19 +1,153 @@
-use zellij_tile::prelude::{SessionInfo, kill_sessions, switch_session};
-use crate::session::types::SessionAction;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+use zellij_tile::prelude::{SessionInfo, delete_dead_session, kill_sessions, rename_session, run_command, switch_session};
+use crate::session::directory_map::SessionDirectoryMap;
+use crate::session::layout::infer_directory_from_layout;
+use crate::session::switch_history::SwitchHistory;
+use crate::session::types::{PendingDeletion, PendingRename, SessionAction};
+
+/// Maximum session name length accepted by Zellij (bytes)
+pub const MAX_SESSION_NAME_LENGTH: usize = 108;
 
 /// Manages session operations and state
 #[derive(Debug, Default)]
 pub struct SessionManager {
     /// Currently known sessions from Zellij
     sessions: Vec<SessionInfo>,
-    /// Session name pending deletion confirmation
-    pending_deletion: Option<String>,
+    /// Exited sessions that can be resurrected, with the time since they exited
+    resurrectable_sessions: Vec<(String, Duration)>,
+    /// Live sessions marked for batch operations
+    marked_sessions: BTreeSet<String>,
+    /// Sessions pending deletion confirmation
+    pending_deletion: Option<PendingDeletion>,
+    /// Session rename being edited
+    pending_rename: Option<PendingRename>,
+    /// Persistent record of the directory each session was created from
+    directory_map: SessionDirectoryMap,
+    /// Directories inferred from the pane cwds of live sessions (None if inference failed)
+    inferred_directories: BTreeMap<String, Option<String>>,
+    /// Sessions whose inferred directory is kept until their layout is dumped again
+    stale_inferred_directories: BTreeSet<String>,
+    /// Sessions whose layout has been requested but not yet received
+    pending_layout_requests: BTreeSet<String>,
+    /// Persistent most recently used order of the sessions switched to
+    switch_history: SwitchHistory,
 }
 
 impl SessionManager {
     /// Update the session list with new session information
     pub fn update_sessions(&mut self, sessions: Vec<SessionInfo>) {
         self.sessions = sessions;
+        // Forget marks and inferred directories of sessions that no longer exist
+        let sessions = &self.sessions;
+        self.marked_sessions.retain(|name| sessions.iter().any(|s| s.name == *name));
+        self.inferred_directories.retain(|name, _| sessions.iter().any(|s| s.name == *name));
+        self.stale_inferred_directories.retain(|name| sessions.iter().any(|s| s.name == *name));
+    }
+
+    /// Infer the directories of live sessions again on the next layout requests, since their
+    /// panes may have moved
+    pub fn expire_inferred_directories(&mut self) {
+        self.stale_inferred_directories = self.inferred_directories.keys().cloned().collect();
+    }
+
+    /// Request the layout of every live session whose directory hasn't been inferred yet
+    /// (or has expired)
+    pub fn request_session_layouts(&mut self) {
+        for session in &self.sessions {
+            let inferred = self.inferred_directories.contains_key(&session.name)
+                && !self.stale_inferred_directories.contains(&session.name);
+            if inferred || self.pending_layout_requests.contains(&session.name) {
+                continue;
+            }
+            
+            let mut context = BTreeMap::new();
+            context.insert("session_layout".to_string(), session.name.clone());
+            run_command(
+                &["zellij", "--session", &session.name, "action", "dump-layout"],
+                context,
+            );
+            self.pending_layout_requests.insert(session.name.clone());
+        }
+    }
+
+    /// Handle a dumped session layout (None if the dump failed)
+    pub fn process_session_layout(&mut self, session_name: &str, layout: Option<&str>) {
+        self.pending_layout_requests.remove(session_name);
+        self.stale_inferred_directories.remove(session_name);
+        if self.sessions.iter().any(|s| s.name == session_name) {
+            let directory = layout.and_then(infer_directory_from_layout);
+            self.inferred_directories.insert(session_name.to_string(), directory);
+        }
+    }
+
+    /// Load the persisted session directories from the plugin data directory
+    pub fn load_directory_map(&mut self) {
+        self.directory_map = SessionDirectoryMap::load();
+    }
+
+    /// Load (or reload) the persisted session switch history from the plugin data directory
+    pub fn load_switch_history(&mut self) {
+        self.switch_history = SwitchHistory::load();
+    }
+
+    /// Record a switch from the current session to another (new or existing) session
+    pub fn record_switch(&mut self, session_name: &str) {
+        let current = self.sessions.iter().find(|s| s.is_current_session).map(|s| s.name.clone());
+        self.switch_history.record(current.as_deref(), session_name);
+    }
+
+    /// Forget the switch history of sessions that are neither live nor resurrectable
+    /// (killed, deleted or renamed outside ZSM)
+    pub fn prune_switch_history(&mut self) {
+        if self.sessions.is_empty() {
+            return; // Not received the session list yet
+        }
+        let (sessions, resurrectable) = (&self.sessions, &self.resurrectable_sessions);
+        self.switch_history.retain(|name| {
+            sessions.iter().any(|s| s.name == name) || resurrectable.iter().any(|(n, _)| n == name)
+        });
+    }
+
+    /// Get the most recently used session other than the current one, if it still exists
+    pub fn previous_session(&self) -> Option<&str> {
+        self.switch_history
+            .sessions()
+            .iter()
+            .map(|name| name.as_str())
+            .find(|name| {
+                let is_live = self.sessions.iter().any(|s| s.name == *name && !s.is_current_session);
+                is_live || self.resurrectable_sessions.iter().any(|(n, _)| n == name)
+            })
+    }
+
+    /// Position of a session in most recently used order (None if never switched to)
+    pub fn recent_position(&self, session_name: &str) -> Option<usize> {
+        self.switch_history.position(session_name)
+    }
+
+    /// Record the directory a session was created from
+    pub fn record_session_directory(&mut self, session_name: &str, directory: &str) {
+        self.directory_map.insert(session_name, directory);
+    }
+
+    /// Get the directory of a session, if known: the directory it was created from,
+    /// otherwise the one inferred from its panes
+    pub fn session_directory(&self, session_name: &str) -> Option<&str> {
+        self.directory_map
+            .directory_for(session_name)
+            .or_else(|| {
+                self.inferred_directories
+                    .get(session_name)
+                    .and_then(|d| d.as_deref())
+            })
+    }
+
+    /// Update the list of resurrectable (exited) sessions
+    pub fn update_resurrectable_sessions(&mut self, mut sessions: Vec<(String, Duration)>) {
+        // Most recently exited first
+        sessions.sort_by_key(|(_, exited_ago)| *exited_ago);
+        self.resurrectable_sessions = sessions;
     }
 
     /// Get all sessions
@@ -21,27 +155,132 @@ impl SessionManager {
         &self.sessions
     }
 
+    /// Get a live session by name
+    pub fn session_info(&self, session_name: &str) -> Option<&SessionInfo> {
+        self.sessions.iter().find(|s| s.name == session_name)
+    }
+
+    /// Get all resurrectable sessions
+    pub fn resurrectable_sessions(&self) -> &[(String, Duration)] {
+        &self.resurrectable_sessions
+    }
+
     /// Execute a session action
     pub fn execute_action(&mut self, action: SessionAction) {
         match action {
             SessionAction::Switch(name) => {
                 switch_session(Some(&name));
             }
-            SessionAction::Kill(name) => {
-                kill_sessions(&[&name]);
+            SessionAction::Kill(names) => {
+                kill_sessions(&names);
+                self.marked_sessions.retain(|name| !names.contains(name));
             }
+            SessionAction::DeleteDead(name) => {
+                delete_dead_session(&name);
+                self.resurrectable_sessions.retain(|(n, _)| *n != name);
+                self.directory_map.remove(&name);
+                self.switch_history.remove(&name);
+            }
+            SessionAction::Rename { from, to, is_current } => {
+                if is_current {
+                    rename_session(&to);
+                } else {
+                    // rename_session only applies to the plugin's own session,
+                    // so other sessions are renamed through the zellij CLI
+                    // (the rename is applied to ZSM's records once it succeeds)
+                    let mut context = BTreeMap::new();
+                    context.insert("session_rename".to_string(), from.clone());
+                    context.insert("session_rename_to".to_string(), to.clone());
+                    run_command(
+                        &["zellij", "--session", &from, "action", "rename-session", &to],
+                        context,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Carry the recorded directory and switch history of a renamed session over to its new name
+    pub fn apply_rename(&mut self, from: &str, to: &str) {
+        self.directory_map.rename(from, to);
+        self.switch_history.rename(from, to);
+    }
+
+    /// Toggle the batch mark on a live session
+    pub fn toggle_mark(&mut self, session_name: &str) {
+        if !self.marked_sessions.remove(session_name) {
+            self.marked_sessions.insert(session_name.to_string());
         }
     }
 
+    /// Check if a session is marked
+    pub fn is_marked(&self, session_name: &str) -> bool {
+        self.marked_sessions.contains(session_name)
+    }
+
+    /// Get all marked sessions
+    pub fn marked_sessions(&self) -> &BTreeSet<String> {
+        &self.marked_sessions
+    }
+
+    /// Clear all marks
+    pub fn clear_marks(&mut self) {
+        self.marked_sessions.clear();
+    }
+
     /// Start session deletion confirmation
-    pub fn start_deletion(&mut self, session_name: String) {
-        self.pending_deletion = Some(session_name);
+    pub fn start_deletion(&mut self, session_names: Vec<String>) {
+        if !session_names.is_empty() {
+            self.pending_deletion = Some(PendingDeletion::Sessions(session_names));
+        }
+    }
+
+    /// Start deletion confirmation for every live session except the current one.
+    /// Returns the number of sessions selected.
+    pub fn start_deletion_except_current(&mut self) -> usize {
+        let names: Vec<String> = self.sessions
+            .iter()
+            .filter(|s| !s.is_current_session)
+            .map(|s| s.name.clone())
+            .collect();
+        let count = names.len();
+        self.start_deletion(names);
+        count
+    }
+
+    /// Start deletion confirmation for a single exited session
+    pub fn start_dead_deletion(&mut self, session_name: String) {
+        self.pending_deletion = Some(PendingDeletion::DeadSessions(vec![session_name]));
+    }
+
+    /// Enter prune mode: select every exited session older than the given age for deletion.
+    /// Returns the number of sessions selected (nothing is pending if none matched).
+    pub fn start_prune(&mut self, older_than: Duration) -> usize {
+        let names: Vec<String> = self.resurrectable_sessions
+            .iter()
+            .filter(|(_, exited_ago)| *exited_ago >= older_than)
+            .map(|(name, _)| name.clone())
+            .collect();
+        let count = names.len();
+        
+        if count > 0 {
+            self.pending_deletion = Some(PendingDeletion::DeadSessions(names));
+        }
+        count
     }
 
     /// Confirm session deletion
     pub fn confirm_deletion(&mut self) {
-        if let Some(session_name) = self.pending_deletion.take() {
-            self.execute_action(SessionAction::Kill(session_name));
+        match self.pending_deletion.take() {
+            Some(PendingDeletion::Sessions(session_names)) => {
+                self.execute_action(SessionAction::Kill(session_names));
+            }
+            Some(PendingDeletion::DeadSessions(session_names)) => {
+                for session_name in session_names {
+                    self.execute_action(SessionAction::DeleteDead(session_name));
+                }
+            }
+            None => {}
         }
     }
 
@@ -50,27 +289,132 @@ impl SessionManager {
         self.pending_deletion = None;
     }
 
-    /// Get session pending deletion
-    pub fn pending_deletion(&self) -> Option<&str> {
-        self.pending_deletion.as_deref()
+    /// Get sessions pending deletion
+    pub fn pending_deletion(&self) -> Option<&PendingDeletion> {
+        self.pending_deletion.as_ref()
+    }
+
+    /// Start editing a rename for the given session
+    pub fn start_rename(&mut self, session_name: String, is_current: bool) {
+        self.pending_rename = Some(PendingRename {
+            new_name: session_name.clone(),
+            session_name,
+            is_current,
+            error: None,
+        });
+    }
+
+    /// Get the rename being edited
+    pub fn pending_rename(&self) -> Option<&PendingRename> {
+        self.pending_rename.as_ref()
+    }
+
+    /// Append a character to the new session name
+    pub fn rename_add_char(&mut self, c: char) {
+        if let Some(rename) = self.pending_rename.as_mut() {
+            rename.new_name.push(c);
+            rename.error = None;
+        }
+    }
+
+    /// Remove the last character of the new session name
+    pub fn rename_backspace(&mut self) {
+        if let Some(rename) = self.pending_rename.as_mut() {
+            rename.new_name.pop();
+            rename.error = None;
+        }
+    }
+
+    /// Validate and start the rename, returning the old and new names if it already took effect
+    /// (the current session). Other sessions are renamed through the zellij CLI, whose result
+    /// arrives later. Keeps the editor open with an error if the name is invalid.
+    pub fn confirm_rename(&mut self) -> Option<(String, String)> {
+        let rename = self.pending_rename.clone()?;
+        
+        let new_name = rename.new_name.trim().to_string();
+        if new_name == rename.session_name {
+            self.pending_rename = None;
+            return None;
+        }
+        
+        match self.validate_session_name(&new_name) {
+            Ok(()) => {
+                self.pending_rename = None;
+                self.execute_action(SessionAction::Rename {
+                    from: rename.session_name.clone(),
+                    to: new_name.clone(),
+                    is_current: rename.is_current,
+                });
+                rename.is_current.then_some((rename.session_name, new_name))
+            }
+            Err(error) => {
+                if let Some(rename) = self.pending_rename.as_mut() {
+                    rename.error = Some(error);
+                }
+                None
+            }
+        }
+    }
+
+    /// Cancel the rename
+    pub fn cancel_rename(&mut self) {
+        self.pending_rename = None;
     }
 
-    /// Check if a session exists for a given directory session name
-    /// Returns the existing session name if found (could be base name or incremented version)
-    pub fn find_existing_session_for_directory(&self, base_name: &str, separator: &str) -> Option<String> {
+    /// Check that a name can be used for a session
+    pub fn validate_session_name(&self, name: &str) -> Result<(), String> {
+        if name.is_empty() {
+            return Err("Session name cannot be empty".to_string());
+        }
+        if name.len() > MAX_SESSION_NAME_LENGTH {
+            return Err(format!("Session name must be at most {} bytes", MAX_SESSION_NAME_LENGTH));
+        }
+        if name.contains('/') {
+            return Err("Session name cannot contain '/'".to_string());
+        }
+        let taken = self.sessions.iter().any(|s| s.name == name)
+            || self.resurrectable_sessions.iter().any(|(n, _)| n == name);
+        if taken {
+            return Err(format!("A session named '{}' already exists", name));
+        }
+        Ok(())
+    }
+
+    /// Check if a session exists for a given directory
+    /// Sessions whose known directory (recorded or inferred from panes) is this one take
+    /// precedence; otherwise the generated session name is matched (base name or incremented
+    /// version), ignoring sessions known to belong to other directories.
+    /// Live sessions are preferred, falling back to resurrectable ones.
+    pub fn find_existing_session_for_directory(&self, directory: &str, base_name: &str, separator: &str) -> Option<String> {
+        if let Some(session) = self.sessions.iter().find(|s| self.session_directory(&s.name) == Some(directory)) {
+            return Some(session.name.clone());
+        }
+        let mapped_sessions = self.directory_map.sessions_for(directory);
+        if let Some((name, _)) = self.resurrectable_sessions.iter().find(|(n, _)| mapped_sessions.contains(&n.as_str())) {
+            return Some(name.clone());
+        }
+        
+        let is_unmapped = |name: &str| self.session_directory(name).is_none();
+        
         // First check for exact match
-        if self.sessions.iter().any(|s| s.name == base_name) {
+        if self.sessions.iter().any(|s| s.name == base_name && is_unmapped(&s.name)) {
             return Some(base_name.to_string());
         }
         
         // Then check for incremented versions
         for session in &self.sessions {
-            if self.is_incremented_session(&session.name, base_name, separator) {
+            if is_unmapped(&session.name) && self.is_incremented_session(&session.name, base_name, separator) {
                 return Some(session.name.clone());
             }
         }
-        
-        None
+
+        // Finally check exited sessions that can be resurrected
+        self.resurrectable_sessions
+            .iter()
+            .map(|(name, _)| name)
+            .filter(|name| is_unmapped(name))
+            .find(|name| *name == base_name || self.is_incremented_session(name, base_name, separator))
+            .cloned()
     }
     
     /// Check if session name is an incremented version of base name
@@ -109,4 +453,83 @@ impl SessionManager {
         // Fallback with UUID if too many increments
         format!("{}{}{}", base_name, separator, uuid::Uuid::new_v4().to_string()[..8].to_string())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager_with_sessions(live: &[&str], exited: &[&str]) -> SessionManager {
+        let mut manager = SessionManager::default();
+        manager.update_sessions(
+            live.iter()
+                .map(|name| SessionInfo { name: name.to_string(), ..Default::default() })
+                .collect(),
+        );
+        manager.update_resurrectable_sessions(
+            exited.iter().map(|name| (name.to_string(), Duration::from_secs(60))).collect(),
+        );
+        manager
+    }
+
+    #[test]
+    fn test_validate_session_name() {
+        let manager = manager_with_sessions(&["app", "api"], &["old"]);
+
+        assert!(manager.validate_session_name("web").is_ok());
+        assert!(manager.validate_session_name("").is_err());
+        assert!(manager.validate_session_name("app").is_err());
+        assert!(manager.validate_session_name("old").is_err());
+        assert!(manager.validate_session_name("a/b").is_err());
+        assert!(manager.validate_session_name(&"x".repeat(MAX_SESSION_NAME_LENGTH)).is_ok());
+        assert!(manager.validate_session_name(&"x".repeat(MAX_SESSION_NAME_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_find_existing_session_for_directory() {
+        let manager = manager_with_sessions(&["app.2"], &["web"]);
+
+        assert_eq!(manager.find_existing_session_for_directory("/p/app", "app", "."), Some("app.2".to_string()));
+        assert_eq!(manager.find_existing_session_for_directory("/p/web", "web", "."), Some("web".to_string()));
+        assert_eq!(manager.find_existing_session_for_directory("/p/api", "api", "."), None);
+    }
+
+    #[test]
+    fn test_find_existing_session_prefers_directory_map() {
+        let mut manager = manager_with_sessions(&["app", "renamed"], &[]);
+        manager.directory_map = SessionDirectoryMap::from_lines(&[
+            "renamed\t/p/app".to_string(),
+            "app\t/q/app".to_string(),
+        ]);
+
+        // Mapped session wins even though its name no longer matches
+        assert_eq!(manager.find_existing_session_for_directory("/p/app", "app", "."), Some("renamed".to_string()));
+        // A session recorded for another directory is not matched by name
+        assert_eq!(manager.find_existing_session_for_directory("/r/app", "app", "."), None);
+    }
+
+    #[test]
+    fn test_find_existing_session_uses_inferred_directory() {
+        let mut manager = manager_with_sessions(&["scratch"], &[]);
+        manager.process_session_layout("scratch", Some("layout {\n    cwd \"/p/app\"\n}\n"));
+
+        assert_eq!(manager.session_directory("scratch"), Some("/p/app"));
+        assert_eq!(manager.find_existing_session_for_directory("/p/app", "app", "."), Some("scratch".to_string()));
+    }
+
+    #[test]
+    fn test_previous_session() {
+        let mut manager = manager_with_sessions(&["web", "api"], &["old"]);
+        manager.sessions[0].is_current_session = true;
+        manager.switch_history = SwitchHistory::from_lines(&[
+            "web".to_string(),
+            "gone".to_string(),
+            "api".to_string(),
+            "old".to_string(),
+        ]);
+
+        // The current session and sessions that no longer exist are skipped
+        assert_eq!(manager.previous_session(), Some("api"));
+        assert_eq!(manager.recent_position("old"), Some(3));
+    }
+}
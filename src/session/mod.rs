@@ -1,5 +1,9 @@
+pub mod directory_map;
+pub mod layout;
 pub mod manager;
+pub mod switch_history;
 pub mod types;
 
 pub use manager::SessionManager;
-pub use types::{SessionAction, SessionItem};
\ No newline at end of file
+pub use switch_history::SessionOrder;
+pub use types::{PendingDeletion, PendingRename, SessionAction, SessionItem, directory_label, format_elapsed};
@@ -0,0 +1,131 @@
+use crate::storage;
+
+/// File in the plugin data directory holding the session switch history
+const SWITCH_HISTORY_FILE: &str = "session_history.tsv";
+
+/// Number of sessions remembered
+const MAX_SESSIONS: usize = 50;
+
+/// How sessions are ordered in the main list
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SessionOrder {
+    /// Sessions linked to a directory first, then the others
+    #[default]
+    Default,
+    /// Most recently used sessions first
+    Mru,
+}
+
+/// Persistent list of sessions switched to through ZSM, most recently used first.
+/// Stored as one session name per line. Every Zellij session runs its own plugin instance, so
+/// changes re-read the file first to keep the switches recorded by the others.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SwitchHistory {
+    sessions: Vec<String>,
+}
+
+impl SwitchHistory {
+    /// Load the history from the plugin data directory
+    pub fn load() -> Self {
+        Self::from_lines(&storage::read_lines(SWITCH_HISTORY_FILE))
+    }
+
+    /// Parse the history from stored lines, skipping duplicates
+    pub fn from_lines(lines: &[String]) -> Self {
+        let mut history = Self::default();
+        for name in lines.iter().map(|line| line.trim()) {
+            if !history.sessions.iter().any(|s| s == name) {
+                history.sessions.push(name.to_string());
+            }
+        }
+        history
+    }
+
+    /// Serialize the history to lines
+    pub fn to_lines(&self) -> Vec<String> {
+        self.sessions.clone()
+    }
+
+    /// Write the history to the plugin data directory
+    fn save(&self) {
+        if let Err(err) = storage::write_lines(SWITCH_HISTORY_FILE, &self.to_lines()) {
+            eprintln!("Warning: Failed to save session history: {}", err);
+        }
+    }
+
+    /// Record a switch from the current session (if known) to another one and save the history
+    pub fn record(&mut self, from: Option<&str>, to: &str) {
+        *self = Self::load();
+        self.push(from, to);
+        self.save();
+    }
+
+    /// Move the sessions of a switch to the front: the target first, then the one left
+    fn push(&mut self, from: Option<&str>, to: &str) {
+        for name in from.into_iter().chain([to]) {
+            self.sessions.retain(|s| s != name);
+            self.sessions.insert(0, name.to_string());
+        }
+        self.sessions.truncate(MAX_SESSIONS);
+    }
+
+    /// Keep a session's place after it was renamed
+    pub fn rename(&mut self, from: &str, to: &str) {
+        *self = Self::load();
+        if from == to || self.position(from).is_none() {
+            return;
+        }
+        self.sessions.retain(|s| s != to);
+        if let Some(index) = self.position(from) {
+            self.sessions[index] = to.to_string();
+        }
+        self.save();
+    }
+
+    /// Forget a session that was deleted
+    pub fn remove(&mut self, session_name: &str) {
+        *self = Self::load();
+        if self.position(session_name).is_some() {
+            self.sessions.retain(|s| s != session_name);
+            self.save();
+        }
+    }
+
+    /// Forget the sessions for which `keep` returns false, saving the history if any were
+    pub fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        *self = Self::load();
+        let len = self.sessions.len();
+        self.sessions.retain(|s| keep(s));
+        if self.sessions.len() != len {
+            self.save();
+        }
+    }
+
+    /// Position of a session in the history (0 = most recently used)
+    pub fn position(&self, session_name: &str) -> Option<usize> {
+        self.sessions.iter().position(|s| s == session_name)
+    }
+
+    /// Sessions in most recently used order
+    pub fn sessions(&self) -> &[String] {
+        &self.sessions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_switch_history() {
+        let mut history = SwitchHistory::from_lines(&["api".to_string(), "web".to_string(), "api".to_string()]);
+        assert_eq!(history.sessions(), &["api", "web"]);
+
+        history.push(Some("web"), "docs");
+        assert_eq!(history.sessions(), &["docs", "web", "api"]);
+        history.push(Some("docs"), "web");
+        assert_eq!(history.sessions(), &["web", "docs", "api"]);
+        assert_eq!(history.position("api"), Some(2));
+        assert_eq!(SwitchHistory::from_lines(&history.to_lines()), history);
+    }
+}
@@ -1,3 +1,6 @@
+use std::time::Duration;
+
+use crate::zoxide::Worktree;
 
 /// Represents different types of items that can be displayed in the session list
 #[derive(Debug, Clone)]
@@ -5,28 +8,154 @@ pub enum SessionItem {
     /// An existing Zellij session
     ExistingSession {
         name: String,
-        directory: String,
+        /// Directory the session belongs to (None for orphan sessions not linked to a directory)
+        directory: Option<String>,
         is_current: bool,
     },
-    /// A zoxide directory that can be used to create a new session
+    /// An exited session that Zellij has serialized and can resurrect
+    ResurrectableSession {
+        name: String,
+        /// Time elapsed since the session exited
+        exited_ago: Duration,
+    },
+    /// A directory (from zoxide or another source) that can be used to create a new session
     Directory {
         path: String,
         session_name: String,
+        /// Tag of the source the directory came from
+        source: String,
+        /// Set when the directory is a linked git worktree
+        worktree: Option<Worktree>,
+        /// Set when the directory no longer exists
+        missing: bool,
     },
 }
 
 impl SessionItem {
-    /// Check if this is an existing session
-    pub fn is_session(&self) -> bool {
-        matches!(self, SessionItem::ExistingSession { .. })
+    /// Ordering group used when mixing item types (live sessions, then orphan sessions,
+    /// then exited sessions, then directories)
+    pub fn group_order(&self) -> u8 {
+        match self {
+            SessionItem::ExistingSession { directory: Some(_), .. } => 0,
+            SessionItem::ExistingSession { directory: None, .. } => 1,
+            SessionItem::ResurrectableSession { .. } => 2,
+            SessionItem::Directory { .. } => 3,
+        }
+    }
+
+    /// Check if two items refer to the same session or directory (ignoring their other state)
+    pub fn is_same_item(&self, other: &SessionItem) -> bool {
+        match (self, other) {
+            (SessionItem::ExistingSession { name: a, .. }, SessionItem::ExistingSession { name: b, .. }) |
+            (SessionItem::ResurrectableSession { name: a, .. }, SessionItem::ResurrectableSession { name: b, .. }) |
+            (SessionItem::Directory { path: a, .. }, SessionItem::Directory { path: b, .. }) => a == b,
+            _ => false,
+        }
     }
 }
 
 /// Actions that can be performed on sessions
 #[derive(Debug, Clone)]
 pub enum SessionAction {
-    /// Switch to an existing session
+    /// Switch to an existing session (or resurrect an exited one)
     Switch(String),
-    /// Kill an existing session
-    Kill(String),
-}
\ No newline at end of file
+    /// Kill existing sessions (in a single request)
+    Kill(Vec<String>),
+    /// Delete an exited (resurrectable) session
+    DeleteDead(String),
+    /// Rename a session
+    Rename {
+        from: String,
+        to: String,
+        is_current: bool,
+    },
+}
+
+/// A session rename being edited inline
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingRename {
+    /// Session being renamed
+    pub session_name: String,
+    /// Whether the session being renamed is the current one
+    pub is_current: bool,
+    /// New name typed so far
+    pub new_name: String,
+    /// Validation error from the last confirmation attempt
+    pub error: Option<String>,
+}
+
+/// Sessions awaiting deletion confirmation
+#[derive(Debug, Clone, PartialEq)]
+pub enum PendingDeletion {
+    /// Live sessions to kill
+    Sessions(Vec<String>),
+    /// Exited sessions to delete permanently
+    DeadSessions(Vec<String>),
+}
+
+impl PendingDeletion {
+    /// Names of all sessions that will be removed
+    pub fn session_names(&self) -> Vec<&str> {
+        match self {
+            PendingDeletion::Sessions(names) | PendingDeletion::DeadSessions(names) => {
+                names.iter().map(|n| n.as_str()).collect()
+            }
+        }
+    }
+}
+
+/// Text shown for a directory row; worktrees are indented under their repository with their branch
+pub fn directory_label(path: &str, worktree: Option<&Worktree>) -> String {
+    match worktree {
+        Some(worktree) => format!("  ↳ {} [{}]", path, worktree.branch.as_deref().unwrap_or("detached")),
+        None => path.to_string(),
+    }
+}
+
+/// Format an elapsed duration in a compact human readable form (e.g. "5m", "3h", "2d")
+pub fn format_elapsed(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 60 * 60 {
+        format!("{}m", secs / 60)
+    } else if secs < 60 * 60 * 24 {
+        format!("{}h", secs / (60 * 60))
+    } else {
+        format!("{}d", secs / (60 * 60 * 24))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_secs(42)), "42s");
+        assert_eq!(format_elapsed(Duration::from_secs(5 * 60 + 10)), "5m");
+        assert_eq!(format_elapsed(Duration::from_secs(3 * 60 * 60)), "3h");
+        assert_eq!(format_elapsed(Duration::from_secs(2 * 24 * 60 * 60 + 5)), "2d");
+    }
+
+    #[test]
+    fn test_is_same_item() {
+        let session = |name: &str, is_current| SessionItem::ExistingSession {
+            name: name.to_string(),
+            directory: None,
+            is_current,
+        };
+        let directory = |path: &str, missing| SessionItem::Directory {
+            path: path.to_string(),
+            session_name: "app".to_string(),
+            source: "zoxide".to_string(),
+            worktree: None,
+            missing,
+        };
+
+        assert!(session("app", false).is_same_item(&session("app", true)));
+        assert!(!session("app", false).is_same_item(&session("web", false)));
+        assert!(directory("/code/app", false).is_same_item(&directory("/code/app", true)));
+        assert!(!session("app", false).is_same_item(&directory("app", false)));
+    }
+}
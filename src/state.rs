@@ -1,11 +1,16 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Duration;
 use zellij_tile::prelude::*;
 
 use crate::config::Config;
 use crate::keybinds::KeyAction;
-use crate::session::{SessionManager, SessionItem, SessionAction};
-use crate::zoxide::{ZoxideDirectory, SearchEngine};
+use crate::session::{SessionManager, SessionItem, SessionAction, SessionOrder, format_elapsed};
+use crate::zoxide::{ZoxideDirectory, SearchEngine, PreviewCache, PreviewState, Worktree, GitCheckout, MissingDirectories, DirectorySource, SortMode, ZoxideEntry, ZoxideError};
+use crate::zoxide::source::merge_sources;
+use crate::zoxide::database::{database_candidates, DatabaseCache};
 use crate::new_session_info::NewSessionInfo;
+use crate::pins::{Pin, PinnedItems};
+use crate::history::UsageHistory;
 
 /// The main plugin state
 pub struct PluginState {
@@ -15,6 +20,8 @@ pub struct PluginState {
     session_manager: SessionManager,
     /// Zoxide directories (managed separately from sessions)
     zoxide_directories: Vec<ZoxideDirectory>,
+    /// Directories listed by each configured source, keyed by source index
+    source_directories: BTreeMap<usize, Vec<ZoxideDirectory>>,
     /// Search engine for fuzzy finding
     search_engine: SearchEngine,
     /// New session creation component
@@ -23,6 +30,10 @@ pub struct PluginState {
     active_screen: ActiveScreen,
     /// Error message to display
     error: Option<String>,
+    /// Remediation hint shown below the error message
+    error_hint: Option<&'static str>,
+    /// Last zoxide error reported, so background refreshes don't report it again
+    zoxide_error: Option<ZoxideError>,
     /// Color scheme
     colors: Option<Palette>,
     /// Current session name
@@ -31,6 +42,28 @@ pub struct PluginState {
     request_ids: Vec<String>,
     /// Selected index in main list (when not searching)
     selected_index: Option<usize>,
+    /// Whether the requested permissions (including RunCommands) were granted
+    permissions_granted: bool,
+    /// Whether zoxide's database is read directly because commands can't be run
+    database_fallback: bool,
+    /// The user's home directory on the host, once read through a command
+    home_directory: Option<String>,
+    /// Last read zoxide database, reparsed only when it changes
+    database_cache: DatabaseCache,
+    /// Lazily fetched git status and project type of directories
+    preview_cache: PreviewCache,
+    /// Linked git worktrees among the zoxide directories, keyed by path
+    worktrees: HashMap<String, Worktree>,
+    /// Git repository and branch of the listed directories inside a working tree, keyed by path
+    git_checkouts: HashMap<String, GitCheckout>,
+    /// Pinned sessions and directories, shown above everything else
+    pins: PinnedItems,
+    /// Listed directories that no longer exist
+    missing_directories: HashSet<String>,
+    /// Current order of listed directories
+    sort_mode: SortMode,
+    /// Sessions and directories selected in ZSM, used to rank them
+    history: UsageHistory,
 }
 
 /// Represents the different screens in the plugin
@@ -54,14 +87,28 @@ impl Default for PluginState {
             config: Config::default(),
             session_manager: SessionManager::default(),
             zoxide_directories: Vec::new(),
+            source_directories: BTreeMap::new(),
             search_engine: SearchEngine::default(),
             new_session_info: NewSessionInfo::default(),
             active_screen: ActiveScreen::default(),
             error: None,
+            error_hint: None,
+            zoxide_error: None,
             colors: None,
             current_session_name: None,
             request_ids: Vec::new(),
             selected_index: None,
+            permissions_granted: false,
+            database_fallback: false,
+            home_directory: None,
+            database_cache: DatabaseCache::default(),
+            preview_cache: PreviewCache::default(),
+            worktrees: HashMap::new(),
+            git_checkouts: HashMap::new(),
+            pins: PinnedItems::default(),
+            missing_directories: HashSet::new(),
+            sort_mode: SortMode::default(),
+            history: UsageHistory::default(),
         }
     }
 }
@@ -70,10 +117,17 @@ impl PluginState {
     /// Initialize plugin with configuration
     pub fn initialize(&mut self, configuration: BTreeMap<String, String>) {
         self.config = Config::from_zellij_config(&configuration);
+        self.sort_mode = self.config.sort_mode;
+        self.session_manager.load_directory_map();
+        self.session_manager.load_switch_history();
+        self.pins = PinnedItems::load();
+        self.search_engine.set_pinned(self.pins.pins().to_vec());
+        self.history = UsageHistory::load();
+        self.search_engine.set_usage_bonuses(self.history.search_bonuses(crate::recency::now()));
     }
 
     /// Update session information
-    pub fn update_sessions(&mut self, sessions: Vec<SessionInfo>) {
+    pub fn update_sessions(&mut self, sessions: Vec<SessionInfo>, resurrectable_sessions: Vec<(String, Duration)>) {
         // Store current session name
         for session in &sessions {
             if session.is_current_session {
@@ -83,14 +137,159 @@ impl PluginState {
             }
         }
 
+        let selected = self.selected_item();
         self.session_manager.update_sessions(sessions);
+        self.session_manager.update_resurrectable_sessions(resurrectable_sessions);
+        self.session_manager.prune_switch_history();
+        if self.permissions_granted {
+            self.session_manager.request_session_layouts();
+        }
+        self.restore_selection(selected);
+    }
+
+    /// Mark permissions as granted, read the home directory and start inferring session directories
+    pub fn set_permissions_granted(&mut self) {
+        self.permissions_granted = true;
+        // The plugin sandbox has no HOME, so ask the host's shell; the result arrives as a
+        // `RunCommandResult` with the `home_directory` context key
+        let mut context = BTreeMap::new();
+        context.insert("home_directory".to_string(), String::new());
+        run_command(&["sh", "-c", "printf %s \"$HOME\""], context);
+        self.session_manager.request_session_layouts();
+        self.request_selected_preview();
+    }
+
+    /// Store the host's home directory, used to expand `~/` in directory patterns and to name
+    /// sessions after paths relative to it
+    pub fn set_home_directory(&mut self, home: &str) {
+        self.config.directory_filter.set_home(home);
+        self.home_directory = Some(home.to_string());
+    }
+
+    /// The user's home directory on the host, once known
+    pub fn home_directory(&self) -> Option<&str> {
+        self.home_directory.as_deref()
+    }
+
+    /// Check if the requested permissions were granted
+    pub fn permissions_granted(&self) -> bool {
+        self.permissions_granted
+    }
+
+    /// Read zoxide's database directly from now on, since commands can't be run
+    pub fn set_database_fallback(&mut self) {
+        self.database_fallback = true;
+    }
+
+    /// Check if zoxide's database is read directly instead of querying zoxide
+    pub fn database_fallback(&self) -> bool {
+        self.database_fallback
+    }
+
+    /// Entries of zoxide's database, read again only when the file changed
+    pub fn zoxide_database_entries(&mut self) -> Result<Vec<ZoxideEntry>, ZoxideError> {
+        let host_folder = get_plugin_ids().initial_cwd;
+        let candidates = database_candidates(self.config.zoxide_database.as_deref(), &host_folder);
+        self.database_cache.read(&candidates)
+    }
+
+    /// Handle the output of a directory preview command (None if it failed)
+    pub fn process_directory_preview(&mut self, path: &str, output: Option<&str>) {
+        self.preview_cache.insert(path, output);
+    }
+
+    /// Get the preview state of a directory, if it has been requested
+    pub fn directory_preview(&self, path: &str) -> Option<PreviewState<'_>> {
+        self.preview_cache.get(path)
+    }
+
+    /// Refetch previews (git status may have changed), starting with the selected directory
+    pub fn expire_previews(&mut self) {
+        self.preview_cache.expire();
+        self.request_selected_preview();
+    }
+
+    /// Infer the directories of live sessions again, keeping the current ones until then
+    pub fn expire_session_directories(&mut self) {
+        self.session_manager.expire_inferred_directories();
+        if self.permissions_granted {
+            self.session_manager.request_session_layouts();
+        }
+    }
+
+    /// Fetch the preview of the selected directory if it isn't cached yet
+    fn request_selected_preview(&mut self) {
+        if !self.permissions_granted {
+            return;
+        }
+        if let Some(SessionItem::Directory { path, .. }) = self.selected_item() {
+            self.preview_cache.request(&path);
+        }
+    }
+
+    /// Carry a session's pin, directory and switch history over to its new name once renamed
+    pub fn apply_session_rename(&mut self, from: &str, to: &str) {
+        self.session_manager.apply_rename(from, to);
+        self.pins.rename_session(from, to);
+        self.search_engine.set_pinned(self.pins.pins().to_vec());
+    }
+
+    /// Handle a dumped session layout used to infer the session's directory
+    pub fn process_session_layout(&mut self, session_name: &str, layout: Option<&str>) {
+        self.session_manager.process_session_layout(session_name, layout);
         self.update_search_if_needed();
     }
 
-    /// Update zoxide directories (managed separately from sessions)
-    pub fn update_zoxide_directories(&mut self, directories: Vec<ZoxideDirectory>) {
+    /// Store the directories listed by a source (dropping those rejected by the configured
+    /// filter) and return all sources merged in priority order.
+    /// Pinned directories that no source lists (anymore) are kept at the end.
+    pub fn update_source_directories(&mut self, index: usize, mut directories: Vec<ZoxideDirectory>) -> Vec<ZoxideDirectory> {
+        directories.retain(|directory| self.config.directory_filter.allows(directory));
+        self.source_directories.insert(index, directories);
+        let pinned: Vec<ZoxideDirectory> = self.pins
+            .directories()
+            .map(|path| ZoxideDirectory {
+                directory: path.to_string(),
+                source: "pin".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        merge_sources(self.source_directories.values().map(|d| d.as_slice()).chain([pinned.as_slice()]))
+    }
+
+    /// Get the current order of listed directories
+    pub fn sort_mode(&self) -> SortMode {
+        self.sort_mode
+    }
+
+    /// Whether the current mode needs access times that none of the zoxide directories have
+    /// (zoxide's database couldn't be read), so it falls back to sorting by score
+    pub fn access_times_unavailable(&self) -> bool {
+        let mut zoxide_directories = self
+            .zoxide_directories
+            .iter()
+            .filter(|d| d.source == DirectorySource::Zoxide.tag())
+            .peekable();
+        self.sort_mode != SortMode::Frequency
+            && zoxide_directories.peek().is_some()
+            && zoxide_directories.all(|d| d.last_accessed.is_none())
+    }
+
+    /// Sort directories in the current mode, blending in the usage history
+    pub fn sort_directories(&self, directories: &mut [ZoxideDirectory]) {
+        self.sort_mode.sort(directories, &self.history, crate::recency::now());
+    }
+
+    /// Update zoxide directories (managed separately from sessions), keeping the selection
+    /// on the same item. Returns false when the list did not change.
+    pub fn update_zoxide_directories(&mut self, directories: Vec<ZoxideDirectory>) -> bool {
+        if directories == self.zoxide_directories {
+            return false;
+        }
+        let selected = self.selected_item();
         self.zoxide_directories = directories;
-        self.update_search_if_needed();
+        self.restore_selection(selected);
+        true
     }
 
     /// Handle key input
@@ -98,16 +297,26 @@ impl PluginState {
         // Clear error on any key press
         if self.error.is_some() {
             self.error = None;
+            self.error_hint = None;
             return true;
         }
 
         // Handle session deletion confirmation
-        if let Some(session_name) = self.session_manager.pending_deletion().map(|s| s.to_string()) {
-            return self.handle_deletion_confirmation(key, &session_name);
+        if self.session_manager.pending_deletion().is_some() {
+            return self.handle_deletion_confirmation(key);
+        }
+
+        // Handle inline session rename
+        if self.session_manager.pending_rename().is_some() {
+            return self.handle_rename_key(key);
         }
 
         match self.active_screen {
-            ActiveScreen::Main => self.handle_main_screen_key(key),
+            ActiveScreen::Main => {
+                let should_render = self.handle_main_screen_key(key);
+                self.request_selected_preview();
+                should_render
+            }
             ActiveScreen::NewSession => self.handle_new_session_key(key),
         }
     }
@@ -139,32 +348,160 @@ impl PluginState {
     fn combined_items(&self) -> Vec<SessionItem> {
         let mut items = Vec::new();
         
-        // First, add existing sessions that match zoxide directories (including incremented ones)
+        let mut orphan_sessions = Vec::new();
+        
+        // First, add existing sessions with a known directory: recorded when the session was
+        // created, inferred from its panes, or matching a zoxide directory by name (including
+        // incremented ones)
         for session in self.session_manager.sessions() {
-            // Check if this session name matches any generated session name from zoxide directories
-            for zoxide_dir in &self.zoxide_directories {
-                // Match exact name or incremented names (e.g., "project" matches "project.2", "project.3", etc.)
-                if session.name == zoxide_dir.session_name || 
-                   self.is_incremented_session(&session.name, &zoxide_dir.session_name) {
-                    items.push(SessionItem::ExistingSession {
-                        name: session.name.clone(),
-                        directory: zoxide_dir.directory.clone(),
-                        is_current: session.is_current_session,
-                    });
-                    break;
-                }
+            let directory = self.session_manager
+                .session_directory(&session.name)
+                .map(|d| d.to_string())
+                .or_else(|| {
+                    // Match exact name or incremented names (e.g., "project" matches "project.2", "project.3", etc.)
+                    self.zoxide_directories
+                        .iter()
+                        .find(|zoxide_dir| {
+                            session.name == zoxide_dir.session_name ||
+                            self.is_incremented_session(&session.name, &zoxide_dir.session_name)
+                        })
+                        .map(|zoxide_dir| zoxide_dir.directory.clone())
+                });
+            
+            let is_orphan = directory.is_none();
+            let item = SessionItem::ExistingSession {
+                name: session.name.clone(),
+                directory,
+                is_current: session.is_current_session,
+            };
+            if is_orphan {
+                orphan_sessions.push(item);
+            } else {
+                items.push(item);
             }
         }
         
-        // Then add all zoxide directories (always show directories, even if sessions exist)
+        // Then add orphan sessions that aren't linked to any directory
+        items.extend(orphan_sessions);
+        
+        // In MRU order, sessions switched to most recently come first, linked or not
+        if self.config.session_order == SessionOrder::Mru {
+            items.sort_by_key(|item| self.recent_position(item));
+        }
+        
+        // Then add exited sessions that Zellij can resurrect (most recently exited first)
+        let first_exited = items.len();
+        for (name, exited_ago) in self.session_manager.resurrectable_sessions() {
+            items.push(SessionItem::ResurrectableSession {
+                name: name.clone(),
+                exited_ago: *exited_ago,
+            });
+        }
+        if self.config.session_order == SessionOrder::Mru {
+            items[first_exited..].sort_by_key(|item| self.recent_position(item));
+        }
+        
+        // Then add all directories (always show directories, even if sessions exist),
+        // grouping linked git worktrees right after their main repository
+        let listed_paths: HashSet<&str> = self.zoxide_directories.iter().map(|d| d.directory.as_str()).collect();
         for dir in &self.zoxide_directories {
-            items.push(SessionItem::Directory {
-                path: dir.directory.clone(),
-                session_name: dir.session_name.clone(),
+            let grouped_under_repository = self.worktrees
+                .get(&dir.directory)
+                .is_some_and(|worktree| listed_paths.contains(worktree.main_repository.as_str()));
+            if grouped_under_repository {
+                continue;
+            }
+            
+            items.push(self.directory_item(dir));
+            for linked in &self.zoxide_directories {
+                let is_linked_worktree = self.worktrees
+                    .get(&linked.directory)
+                    .is_some_and(|worktree| worktree.main_repository == dir.directory);
+                if is_linked_worktree {
+                    items.push(self.directory_item(linked));
+                }
+            }
+        }
+        
+        // Leave out directories that no longer exist, if configured (pinned ones stay visible)
+        if self.config.missing_directories == MissingDirectories::Hide {
+            items.retain(|item| {
+                !matches!(item, SessionItem::Directory { missing: true, .. }) || self.pins.is_pinned(item)
             });
         }
         
-        items
+        // Finally move pinned items to a top section, in the order they were pinned
+        let (mut pinned, unpinned): (Vec<_>, Vec<_>) = items
+            .into_iter()
+            .partition(|item| self.pins.is_pinned(item));
+        pinned.sort_by_key(|item| self.pins.position(item));
+        pinned.extend(unpinned);
+        pinned
+    }
+
+    /// Position of a session in most recently used order (sessions never switched to last)
+    fn recent_position(&self, item: &SessionItem) -> usize {
+        match item {
+            SessionItem::ExistingSession { name, .. } | SessionItem::ResurrectableSession { name, .. } => {
+                self.session_manager.recent_position(name).unwrap_or(usize::MAX)
+            }
+            SessionItem::Directory { .. } => usize::MAX,
+        }
+    }
+
+    /// Build the list item for a zoxide directory
+    fn directory_item(&self, dir: &ZoxideDirectory) -> SessionItem {
+        SessionItem::Directory {
+            path: dir.directory.clone(),
+            session_name: dir.session_name.clone(),
+            source: dir.source.clone(),
+            worktree: self.worktrees.get(&dir.directory).cloned(),
+            missing: self.missing_directories.contains(&dir.directory),
+        }
+    }
+
+    /// Update the set of listed directories that no longer exist
+    pub fn update_missing_directories(&mut self, missing: HashSet<String>) {
+        let selected = self.selected_item();
+        self.missing_directories = missing;
+        self.restore_selection(selected);
+    }
+
+    /// Check if there are listed directories that no longer exist
+    pub fn has_missing_directories(&self) -> bool {
+        !self.missing_directories.is_empty()
+    }
+
+    /// Get the pinned sessions and directories
+    pub fn pins(&self) -> &PinnedItems {
+        &self.pins
+    }
+
+    /// Get the zoxide directories
+    pub fn zoxide_directories(&self) -> &[ZoxideDirectory] {
+        &self.zoxide_directories
+    }
+
+    /// Get the linked git worktree info of a directory, if it is one
+    pub fn worktree(&self, path: &str) -> Option<&Worktree> {
+        self.worktrees.get(path)
+    }
+
+    /// Get the git repository and branch of a directory, if it is inside a working tree
+    pub fn git_checkout(&self, path: &str) -> Option<&GitCheckout> {
+        self.git_checkouts.get(path)
+    }
+
+    /// Update the known git repositories and branches (keyed by directory path)
+    pub fn update_git_checkouts(&mut self, git_checkouts: HashMap<String, GitCheckout>) {
+        self.git_checkouts = git_checkouts;
+    }
+
+    /// Update the known git worktrees (keyed by directory path)
+    pub fn update_worktrees(&mut self, worktrees: HashMap<String, Worktree>) {
+        let selected = self.selected_item();
+        self.worktrees = worktrees;
+        self.restore_selection(selected);
     }
 
     /// Check if session name is an incremented version of base name  
@@ -219,6 +556,13 @@ impl PluginState {
     /// Show error message
     pub fn set_error(&mut self, error: String) {
         self.error = Some(error);
+        self.error_hint = None;
+    }
+
+    /// Set error message with a remediation hint
+    pub fn set_error_with_hint(&mut self, error: String, hint: &'static str) {
+        self.error = Some(error);
+        self.error_hint = Some(hint);
     }
 
     /// Get current error
@@ -226,7 +570,23 @@ impl PluginState {
         self.error.as_deref()
     }
 
+    /// Get the remediation hint of the current error
+    pub fn error_hint(&self) -> Option<&str> {
+        self.error_hint
+    }
 
+    /// Report a zoxide error, unless it is the same as the last one reported
+    pub fn report_zoxide_error(&mut self, error: ZoxideError) {
+        if self.zoxide_error.as_ref() != Some(&error) {
+            self.set_error_with_hint(error.to_string(), error.hint());
+            self.zoxide_error = Some(error);
+        }
+    }
+
+    /// Forget the last zoxide error after zoxide was queried successfully
+    pub fn clear_zoxide_error(&mut self) {
+        self.zoxide_error = None;
+    }
 
     /// Get selected item
     pub fn selected_item(&self) -> Option<SessionItem> {
@@ -260,6 +620,50 @@ impl PluginState {
                     self.handle_delete_key();
                     true
                 }
+                KeyAction::RenameSession => {
+                    if let Some(SessionItem::ExistingSession { name, is_current, .. }) = self.selected_item() {
+                        self.session_manager.start_rename(name, is_current);
+                    }
+                    true
+                }
+                KeyAction::ToggleMark => {
+                    self.handle_toggle_mark();
+                    true
+                }
+                KeyAction::TogglePin => {
+                    self.handle_toggle_pin();
+                    true
+                }
+                KeyAction::CycleSortMode => {
+                    self.handle_cycle_sort_mode();
+                    true
+                }
+                KeyAction::PreviousSession => {
+                    self.handle_previous_session_key();
+                    true
+                }
+                KeyAction::KillAllExceptCurrent => {
+                    if self.session_manager.start_deletion_except_current() == 0 {
+                        self.set_error("No other sessions to kill".to_string());
+                    }
+                    true
+                }
+                KeyAction::PruneSessions => {
+                    self.handle_prune_key();
+                    true
+                }
+                KeyAction::RemoveMissingDirectories => {
+                    self.handle_remove_missing_key();
+                    true
+                }
+                KeyAction::RemoveFromZoxide => {
+                    self.handle_remove_from_zoxide_key();
+                    true
+                }
+                KeyAction::AddToZoxide => {
+                    self.handle_add_to_zoxide_key();
+                    true
+                }
                 KeyAction::Exit => {
                     hide_self();
                     false
@@ -268,6 +672,9 @@ impl PluginState {
                     if self.search_engine.is_searching() {
                         self.search_engine.clear();
                         true
+                    } else if !self.session_manager.marked_sessions().is_empty() {
+                        self.session_manager.clear_marks();
+                        true
                     } else {
                         hide_self();
                         false
@@ -298,7 +705,10 @@ impl PluginState {
             match action {
                 KeyAction::Confirm => {
                     // Handle session creation
-                    self.new_session_info.handle_selection(&self.current_session_name);
+                    if let Some((name, folder)) = self.new_session_info.handle_selection(&self.current_session_name) {
+                        self.session_manager.record_session_directory(&name, &folder.to_string_lossy());
+                        self.record_selection(&name, Some(&folder.to_string_lossy()));
+                    }
                     self.active_screen = ActiveScreen::Main;
                     true
                 }
@@ -336,7 +746,7 @@ impl PluginState {
     }
 
     /// Handle deletion confirmation
-    fn handle_deletion_confirmation(&mut self, key: KeyWithModifier, _session_name: &str) -> bool {
+    fn handle_deletion_confirmation(&mut self, key: KeyWithModifier) -> bool {
         match key.bare_key {
             BareKey::Char('y') | BareKey::Char('Y') if key.key_modifiers.is_empty() => {
                 self.session_manager.confirm_deletion();
@@ -350,6 +760,31 @@ impl PluginState {
         }
     }
 
+    /// Handle key input while editing a session rename
+    fn handle_rename_key(&mut self, key: KeyWithModifier) -> bool {
+        match key.bare_key {
+            BareKey::Enter if key.key_modifiers.is_empty() => {
+                if let Some((from, to)) = self.session_manager.confirm_rename() {
+                    self.apply_session_rename(&from, &to);
+                }
+                true
+            }
+            BareKey::Esc if key.key_modifiers.is_empty() => {
+                self.session_manager.cancel_rename();
+                true
+            }
+            BareKey::Backspace => {
+                self.session_manager.rename_backspace();
+                true
+            }
+            BareKey::Char(c) if key.key_modifiers.is_empty() => {
+                self.session_manager.rename_add_char(c);
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Move selection up
     fn move_selection_up(&mut self) {
         if self.search_engine.is_searching() {
@@ -399,7 +834,8 @@ impl PluginState {
         // Get the selected item data before any mutable borrows
         let selected_item_data = self.selected_item().map(|item| {
             match item {
-                SessionItem::ExistingSession { name, .. } => {
+                SessionItem::ExistingSession { name, .. } |
+                SessionItem::ResurrectableSession { name, .. } => {
                     (true, name, String::new())
                 }
                 SessionItem::Directory { session_name, path, .. } => {
@@ -410,48 +846,196 @@ impl PluginState {
 
         if let Some((is_session, name, path)) = selected_item_data {
             if is_session {
-                // Switch to existing session
+                let directory = self.session_manager.session_directory(&name).map(|d| d.to_string());
+                self.record_selection(&name, directory.as_deref());
+                // Switch to existing session (Zellij resurrects exited sessions on switch)
                 self.session_manager.execute_action(SessionAction::Switch(name));
                 hide_self();
             } else {
                 // Check if a session already exists for this directory
                 if let Some(existing_session_name) = self.session_manager
-                    .find_existing_session_for_directory(&name, &self.config.session_separator) {
-                    // Switch to existing session instead of creating a new one
+                    .find_existing_session_for_directory(&path, &name, &self.config.session_separator) {
+                    self.record_selection(&existing_session_name, Some(&path));
+                    // Switch to (or resurrect) the existing session instead of creating a new one
                     self.session_manager.execute_action(SessionAction::Switch(existing_session_name));
                     hide_self();
+                } else if self.missing_directories.contains(&path) {
+                    self.set_error(format!("Directory no longer exists: {}", path));
                 } else {
                     // No existing session found, create new session using quick create logic
                     self.new_session_info.set_name(&name);
                     self.new_session_info.set_folder(Some(std::path::PathBuf::from(&path)));
-                    self.new_session_info.handle_quick_session_creation(&self.current_session_name, &self.config.default_layout);
+                    if let Some((name, folder)) = self.new_session_info.handle_quick_session_creation(&self.current_session_name, &self.config.default_layout) {
+                        self.session_manager.record_session_directory(&name, &folder.to_string_lossy());
+                        self.record_selection(&name, Some(&folder.to_string_lossy()));
+                    }
                 }
             }
         }
     }
 
-    /// Handle delete key
+    /// Handle delete key - kills all marked sessions if any, otherwise the selected one
     fn handle_delete_key(&mut self) {
-        // Get the selected item data before any mutable borrows
-        let selected_session_name = self.selected_item().and_then(|item| {
-            match item {
-                SessionItem::ExistingSession { name, .. } => Some(name),
-                _ => None,
+        let marked: Vec<String> = self.session_manager.marked_sessions().iter().cloned().collect();
+        if !marked.is_empty() {
+            self.session_manager.start_deletion(marked);
+            return;
+        }
+
+        match self.selected_item() {
+            Some(SessionItem::ExistingSession { name, .. }) => {
+                self.session_manager.start_deletion(vec![name]);
             }
-        });
+            Some(SessionItem::ResurrectableSession { name, .. }) => {
+                self.session_manager.start_dead_deletion(name);
+            }
+            _ => {}
+        }
+    }
+
+    /// Toggle the mark on the selected session and advance to the next row
+    fn handle_toggle_mark(&mut self) {
+        if let Some(SessionItem::ExistingSession { name, .. }) = self.selected_item() {
+            self.session_manager.toggle_mark(&name);
+            self.move_selection_down();
+        }
+    }
+
+    /// Handle pin key - pin or unpin the selected session or directory
+    fn handle_toggle_pin(&mut self) {
+        let Some(item) = self.selected_item() else {
+            return;
+        };
+        let pin = Pin::for_item(&item);
+        self.pins.toggle(pin.clone());
+        self.search_engine.set_pinned(self.pins.pins().to_vec());
+        
+        if self.search_engine.is_searching() {
+            self.update_search_if_needed();
+        } else {
+            // Keep the selection on the item as it moves in or out of the pinned section
+            self.selected_index = self.combined_items()
+                .iter()
+                .position(|i| Pin::for_item(i) == pin)
+                .or(self.selected_index);
+        }
+    }
+
+    /// Handle previous session key - switch back to the most recently used other session
+    fn handle_previous_session_key(&mut self) {
+        // Pick up the switches made in other sessions since the history was loaded
+        self.session_manager.load_switch_history();
+        let Some(name) = self.session_manager.previous_session().map(|name| name.to_string()) else {
+            self.set_error("No previous session to switch to".to_string());
+            return;
+        };
+        let directory = self.session_manager.session_directory(&name).map(|d| d.to_string());
+        self.record_selection(&name, directory.as_deref());
+        self.session_manager.execute_action(SessionAction::Switch(name));
+        hide_self();
+    }
+
+    /// Handle sort mode key - switch to the next sort mode and reorder the directories
+    fn handle_cycle_sort_mode(&mut self) {
+        self.sort_mode = self.sort_mode.next();
+        let mut directories = self.zoxide_directories.clone();
+        self.sort_directories(&mut directories);
+        self.update_zoxide_directories(directories);
+    }
+
+    /// Handle remove missing key - remove directories that no longer exist from zoxide
+    fn handle_remove_missing_key(&mut self) {
+        let zoxide_tag = DirectorySource::Zoxide.tag();
+        let missing: Vec<&str> = self.zoxide_directories
+            .iter()
+            .filter(|d| d.source == zoxide_tag && self.missing_directories.contains(&d.directory))
+            .map(|d| d.directory.as_str())
+            .collect();
+        if missing.is_empty() {
+            self.set_error("No missing zoxide directories to remove".to_string());
+            return;
+        }
+        
+        self.update_zoxide("remove", &missing);
+    }
 
-        if let Some(session_name) = selected_session_name {
-            self.session_manager.start_deletion(session_name);
+    /// Handle zoxide remove key - remove the selected directory from zoxide
+    fn handle_remove_from_zoxide_key(&mut self) {
+        if let Some(SessionItem::Directory { path, source, .. }) = self.selected_item() {
+            if source == DirectorySource::Zoxide.tag() {
+                self.update_zoxide("remove", &[&path]);
+            } else {
+                self.set_error(format!("'{}' is listed by {}, not zoxide", path, source));
+            }
+        }
+    }
+
+    /// Handle zoxide add key - add the selected directory to zoxide (or bump its score)
+    fn handle_add_to_zoxide_key(&mut self) {
+        if let Some(SessionItem::Directory { path, .. }) = self.selected_item() {
+            self.update_zoxide("add", &[&path]);
+        }
+    }
+
+    /// Record a session (and its directory) picked in ZSM in the usage history
+    fn record_selection(&mut self, session: &str, path: Option<&str>) {
+        let now = crate::recency::now();
+        self.session_manager.record_switch(session);
+        self.history.record(session, path, now);
+        self.search_engine.set_usage_bonuses(self.history.search_bonuses(now));
+        if let Some(path) = path {
+            self.record_directory_visit(path);
+        }
+    }
+
+    /// Feed a directory opened through ZSM back into zoxide's ranking (no shell `cd` happens)
+    fn record_directory_visit(&self, path: &str) {
+        if !self.config.zoxide_add_on_switch {
+            return;
         }
+        let mut context = BTreeMap::new();
+        context.insert("zoxide_visit".to_string(), path.to_string());
+        self.config.zoxide.run(&["add", path], context);
     }
 
-    /// Update search if currently searching
+    /// Run `zoxide <subcommand> <paths>`; the list is reloaded once it succeeds
+    fn update_zoxide(&self, subcommand: &str, paths: &[&str]) {
+        let mut args = vec![subcommand];
+        args.extend(paths);
+        let mut context = BTreeMap::new();
+        context.insert("zoxide_update".to_string(), subcommand.to_string());
+        self.config.zoxide.run(&args, context);
+    }
+
+    /// Handle prune key - select all exited sessions older than the configured age
+    fn handle_prune_key(&mut self) {
+        let older_than = self.config.prune_older_than;
+        if self.session_manager.start_prune(older_than) == 0 {
+            self.set_error(format!("No exited sessions older than {} to prune", format_elapsed(older_than)));
+        }
+    }
+
+    /// Update search if currently searching, keeping the selected result when it still matches
     fn update_search_if_needed(&mut self) {
         if self.search_engine.is_searching() {
-            let term = self.search_engine.search_term().to_string();
             let items = self.combined_items(); // Always use full item list, not search results
-            self.search_engine.update_search(term, &items);
+            self.search_engine.refresh(&items);
+        }
+    }
+
+    /// Move the list selection back to a previously selected item after the items changed,
+    /// and fetch the preview of whatever ends up selected
+    fn restore_selection(&mut self, selected: Option<SessionItem>) {
+        self.update_search_if_needed();
+        if !self.search_engine.is_searching() {
+            if let Some(selected) = selected {
+                if let Some(index) = self.combined_items().iter().position(|item| item.is_same_item(&selected)) {
+                    self.selected_index = Some(index);
+                }
+            }
         }
+        // The selected row may be a different directory now
+        self.request_selected_preview();
     }
 
     /// Launch filepicker for new session folder selection
@@ -0,0 +1,32 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Zellij mounts a persistent, per-plugin data directory at this path
+const DATA_DIR: &str = "/data";
+
+/// Get the full path of a file in the plugin data directory
+pub fn data_path(file_name: &str) -> PathBuf {
+    PathBuf::from(DATA_DIR).join(file_name)
+}
+
+/// Read all non-empty lines of a data file (a missing file reads as empty)
+pub fn read_lines(file_name: &str) -> Vec<String> {
+    fs::read_to_string(data_path(file_name))
+        .map(|contents| {
+            contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Replace the contents of a data file with the given lines
+pub fn write_lines(file_name: &str, lines: &[String]) -> io::Result<()> {
+    fs::create_dir_all(DATA_DIR)?;
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+    fs::write(data_path(file_name), contents)
+}
@@ -1,7 +1,9 @@
 pub mod components;
+pub mod preview;
 pub mod renderer;
 pub mod theme;
 
 pub use components::Colors;
+pub use preview::PreviewRenderer;
 pub use renderer::PluginRenderer;
 pub use theme::Theme;
\ No newline at end of file
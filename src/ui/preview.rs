@@ -0,0 +1,190 @@
+use zellij_tile::prelude::{print_text_with_coordinates, SessionInfo, Text};
+
+use crate::session::SessionItem;
+use crate::state::PluginState;
+use crate::ui::Theme;
+use crate::zoxide::PreviewState;
+use crate::zoxide::preview::DirectoryPreview;
+
+/// Minimum total width before the preview panel is shown next to the list
+const MIN_WIDTH_FOR_PREVIEW: usize = 100;
+
+/// Renders the preview panel for the selected item
+pub struct PreviewRenderer;
+
+impl PreviewRenderer {
+    /// Width reserved for the preview panel (0 when the plugin is too narrow)
+    pub fn preview_width(total_width: usize) -> usize {
+        if total_width >= MIN_WIDTH_FOR_PREVIEW {
+            total_width * 2 / 5
+        } else {
+            0
+        }
+    }
+
+    /// Render the preview for the selected item
+    pub fn render(state: &PluginState, x: usize, y: usize, width: usize, height: usize, theme: &Option<Theme>) {
+        let lines = match state.selected_item() {
+            Some(SessionItem::ExistingSession { name, directory, .. }) => {
+                match state.session_manager().session_info(&name) {
+                    Some(session) => Self::session_lines(session, directory.as_deref()),
+                    None => return,
+                }
+            }
+            Some(SessionItem::Directory { path, session_name, source, missing: true, .. }) => {
+                Self::missing_directory_lines(&path, &session_name, &source)
+            }
+            Some(SessionItem::Directory { path, session_name, source, .. }) => {
+                Self::directory_lines(&path, &session_name, &source, state.directory_preview(&path))
+            }
+            _ => return,
+        };
+
+        // Separator between the list and the preview
+        for row in 0..height {
+            print_text_with_coordinates(Text::new("│").color_range(0, ..), x, y + row, None, None);
+        }
+
+        let content_x = x + 2;
+        let content_width = width.saturating_sub(2);
+        for (i, line) in lines.into_iter().take(height).enumerate() {
+            let truncated: String = line.text.chars().take(content_width).collect();
+            let text = match (line.style, theme) {
+                (LineStyle::Title, Some(theme)) => theme.title(&truncated),
+                (LineStyle::Title, None) => Text::new(&truncated).color_range(3, ..),
+                (LineStyle::Highlight, Some(theme)) => theme.current_session(&truncated),
+                (LineStyle::Highlight, None) => Text::new(&truncated).color_range(2, ..),
+                (LineStyle::Dim, _) => Text::new(&truncated).color_range(0, ..),
+                (LineStyle::Normal, _) => Text::new(&truncated),
+            };
+            print_text_with_coordinates(text, content_x, y + i, None, None);
+        }
+    }
+
+    /// Build preview lines for a live session: clients, tabs and their panes
+    fn session_lines(session: &SessionInfo, directory: Option<&str>) -> Vec<PreviewLine> {
+        let mut lines = vec![PreviewLine::title(&session.name)];
+        if let Some(directory) = directory {
+            lines.push(PreviewLine::dim(directory));
+        }
+        let clients = match session.connected_clients {
+            1 => "1 connected client".to_string(),
+            n => format!("{} connected clients", n),
+        };
+        lines.push(PreviewLine::normal(&clients));
+        lines.push(PreviewLine::normal(""));
+
+        let mut tabs: Vec<_> = session.tabs.iter().collect();
+        tabs.sort_by_key(|tab| tab.position);
+        for tab in tabs {
+            let tab_line = format!("{} {}", if tab.active { "▸" } else { "▹" }, tab.name);
+            lines.push(if tab.active {
+                PreviewLine::highlight(&tab_line)
+            } else {
+                PreviewLine::normal(&tab_line)
+            });
+
+            let panes = session.panes.panes.get(&tab.position).map(|p| p.as_slice()).unwrap_or_default();
+            for pane in panes.iter().filter(|p| !p.is_plugin && !p.is_suppressed) {
+                let label = pane.terminal_command.as_deref().unwrap_or(&pane.title);
+                let pane_line = format!("    {}{}", label, if pane.is_focused { " *" } else { "" });
+                lines.push(PreviewLine::dim(&pane_line));
+            }
+        }
+
+        lines
+    }
+
+    /// Build preview lines for a directory: git status and detected project type
+    fn directory_lines(path: &str, session_name: &str, source: &str, preview: Option<PreviewState>) -> Vec<PreviewLine> {
+        let mut lines = vec![
+            PreviewLine::title(path),
+            PreviewLine::dim(&format!("Session name: {}", session_name)),
+            PreviewLine::dim(&format!("Source: {}", source)),
+            PreviewLine::normal(""),
+        ];
+
+        match preview {
+            None | Some(PreviewState::Loading) => lines.push(PreviewLine::dim("Loading...")),
+            Some(PreviewState::Unavailable) => lines.push(PreviewLine::dim("Directory could not be read")),
+            Some(PreviewState::Ready(preview)) => lines.extend(Self::directory_preview_lines(preview)),
+        }
+
+        lines
+    }
+
+    /// Build preview lines for a directory that no longer exists
+    fn missing_directory_lines(path: &str, session_name: &str, source: &str) -> Vec<PreviewLine> {
+        vec![
+            PreviewLine::title(path),
+            PreviewLine::dim(&format!("Session name: {}", session_name)),
+            PreviewLine::dim(&format!("Source: {}", source)),
+            PreviewLine::normal(""),
+            PreviewLine::highlight("Directory no longer exists"),
+        ]
+    }
+
+    fn directory_preview_lines(preview: &DirectoryPreview) -> Vec<PreviewLine> {
+        let mut lines = Vec::new();
+
+        match &preview.git {
+            Some(git) => {
+                let branch = git.branch.as_deref().unwrap_or("(detached)");
+                lines.push(PreviewLine::highlight(&format!("Branch: {}", branch)));
+                let status = if git.is_dirty() {
+                    format!("Status: dirty ({} changed)", git.changed_files)
+                } else {
+                    "Status: clean".to_string()
+                };
+                lines.push(PreviewLine::normal(&status));
+                if git.ahead > 0 || git.behind > 0 {
+                    lines.push(PreviewLine::normal(&format!("Ahead {} • Behind {}", git.ahead, git.behind)));
+                }
+            }
+            None => lines.push(PreviewLine::dim("Not a git repository")),
+        }
+
+        if !preview.project_types.is_empty() {
+            lines.push(PreviewLine::normal(&format!("Project: {}", preview.project_types.join(", "))));
+        }
+
+        lines
+    }
+}
+
+/// Visual style of a preview line
+#[derive(Clone, Copy)]
+enum LineStyle {
+    Title,
+    Highlight,
+    Normal,
+    Dim,
+}
+
+/// A single line of preview content
+struct PreviewLine {
+    text: String,
+    style: LineStyle,
+}
+
+impl PreviewLine {
+    fn new(text: &str, style: LineStyle) -> Self {
+        Self { text: text.to_string(), style }
+    }
+
+    fn title(text: &str) -> Self {
+        Self::new(text, LineStyle::Title)
+    }
+
+    fn highlight(text: &str) -> Self {
+        Self::new(text, LineStyle::Highlight)
+    }
+
+    fn normal(text: &str) -> Self {
+        Self::new(text, LineStyle::Normal)
+    }
+
+    fn dim(text: &str) -> Self {
+        Self::new(text, LineStyle::Dim)
+    }
+}
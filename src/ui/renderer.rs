@@ -1,9 +1,12 @@
 use zellij_tile::prelude::{print_text_with_coordinates, print_table_with_coordinates, Table, Text, Palette};
 
 use crate::keybinds::KeyAction;
-use crate::session::SessionItem;
+use crate::session::{PendingDeletion, PendingRename, SessionItem, directory_label, format_elapsed};
 use crate::state::{PluginState, ActiveScreen};
-use crate::ui::{Colors, Theme};
+use crate::ui::{Colors, PreviewRenderer, Theme};
+
+/// Width reserved for the source column (longest tag is "bookmark")
+const SOURCE_COLUMN_WIDTH: usize = 9;
 
 /// Main renderer for the plugin UI
 pub struct PluginRenderer;
@@ -24,9 +27,11 @@ impl PluginRenderer {
 
         // Render overlays
         if let Some(error) = state.error() {
-            Self::render_error(error, x, y, width, height);
-        } else if let Some(session_name) = state.session_manager().pending_deletion() {
-            Self::render_deletion_confirmation(session_name, x, y, width, height, state.colors());
+            Self::render_error(error, state.error_hint(), x, y, width, height);
+        } else if let Some(pending_deletion) = state.session_manager().pending_deletion() {
+            Self::render_deletion_confirmation(pending_deletion, x, y, width, height, state.colors());
+        } else if let Some(pending_rename) = state.session_manager().pending_rename() {
+            Self::render_rename_dialog(pending_rename, x, y, width, height);
         }
     }
 
@@ -42,6 +47,20 @@ impl PluginRenderer {
         };
         print_text_with_coordinates(title, x, y, None, None);
         
+        // Render the sort mode next to the title
+        let sort_keys = state.config().keybinds.format_keys_for_action(KeyAction::CycleSortMode);
+        let sort_text = format!("Sort: {} ({})", state.sort_mode().label(), sort_keys);
+        let sort_text_len = sort_text.len();
+        let sort_indication = if state.access_times_unavailable() {
+            // Without zoxide's database, recency and frecency can only sort by score
+            Text::new(format!("{} - no access times, db.zo unreadable", sort_text))
+                .color_range(0, ..5)
+                .color_range(3, sort_text_len..)
+        } else {
+            Text::new(&sort_text).color_range(0, ..5)
+        };
+        print_text_with_coordinates(sort_indication, x + "Zoxide Session Manager".len() + 2, y, None, None);
+        
         // Render search indication
         let search_term = state.search_engine().search_term();
         let search_text = format!("Search: {}_", search_term);
@@ -52,12 +71,14 @@ impl PluginRenderer {
         };
         print_text_with_coordinates(search_indication, x, y + 2, None, None);
         
-        // Render main content
+        // Render main content, leaving room for the preview panel when wide enough
         let table_rows = height.saturating_sub(6);
+        let preview_width = PreviewRenderer::preview_width(width);
+        let list_width = width.saturating_sub(preview_width);
         let table = if state.search_engine().is_searching() {
-            Self::render_search_results(state, table_rows, width, &theme)
+            Self::render_search_results(state, table_rows, list_width, &theme)
         } else {
-            Self::render_all_items(state, table_rows, width, &theme)
+            Self::render_all_items(state, table_rows, list_width, &theme)
         };
         
         if state.display_items().is_empty() && !state.search_engine().is_searching() {
@@ -69,7 +90,11 @@ impl PluginRenderer {
             };
             print_text_with_coordinates(no_dirs_text, x, y + 4, None, None);
         } else {
-            print_table_with_coordinates(table, x, y + 4, Some(width), Some(table_rows));
+            print_table_with_coordinates(table, x, y + 4, Some(list_width), Some(table_rows));
+        }
+        
+        if preview_width > 0 {
+            PreviewRenderer::render(state, x + list_width, y + 4, preview_width, table_rows, &theme);
         }
         
         // Render help text
@@ -91,7 +116,7 @@ impl PluginRenderer {
 
     /// Render search results table
     fn render_search_results(state: &PluginState, table_rows: usize, table_width: usize, theme: &Option<Theme>) -> Table {
-        let mut table = Table::new().add_row(vec!["Directory/Session"]);
+        let mut table = Table::new().add_row(vec![" ", "Directory/Session", "Source"]);
         let results = state.search_engine().results();
         let selected_index = state.search_engine().selected_index();
         
@@ -105,7 +130,9 @@ impl PluginRenderer {
             if let Some(result) = results.get(i) {
                 let is_selected = Some(i) == selected_index;
                 let mut table_cells = vec![
-                    Self::render_search_result_item(&result.item, &result.indices, table_width.saturating_sub(4), theme)
+                    Self::render_marker(state, &result.item),
+                    Self::render_search_result_item(&result.item, &result.indices, table_width.saturating_sub(SOURCE_COLUMN_WIDTH + 6), theme),
+                    Self::render_source(&result.item),
                 ];
                 
                 if is_selected {
@@ -121,7 +148,7 @@ impl PluginRenderer {
 
     /// Render all items table
     fn render_all_items(state: &PluginState, table_rows: usize, table_width: usize, theme: &Option<Theme>) -> Table {
-        let mut table = Table::new().add_row(vec!["Directory/Session"]);
+        let mut table = Table::new().add_row(vec![" ", "Directory/Session", "Source"]);
         let items = state.display_items();
         let selected_index = state.selected_index();
         
@@ -135,7 +162,9 @@ impl PluginRenderer {
             if let Some(item) = items.get(i) {
                 let is_selected = Some(i) == selected_index;
                 let mut table_cells = vec![
-                    Self::render_item(item, table_width.saturating_sub(4), theme)
+                    Self::render_marker(state, item),
+                    Self::render_item(item, table_width.saturating_sub(SOURCE_COLUMN_WIDTH + 6), theme),
+                    Self::render_source(item),
                 ];
                 
                 if is_selected {
@@ -149,6 +178,25 @@ impl PluginRenderer {
         table
     }
 
+    /// Render the marker column cell for batch-marked sessions and pinned items
+    fn render_marker(state: &PluginState, item: &SessionItem) -> Text {
+        match item {
+            SessionItem::ExistingSession { name, .. } if state.session_manager().is_marked(name) => {
+                Text::new("*").color_range(1, ..)
+            }
+            _ if state.pins().is_pinned(item) => Text::new("★").color_range(2, ..),
+            _ => Text::new(" "),
+        }
+    }
+
+    /// Render the source column cell showing where a directory came from
+    fn render_source(item: &SessionItem) -> Text {
+        match item {
+            SessionItem::Directory { source, .. } => Text::new(source).color_range(0, ..),
+            _ => Text::new(" "),
+        }
+    }
+
     /// Render a search result item
     fn render_search_result_item(item: &SessionItem, indices: &[usize], max_width: usize, theme: &Option<Theme>) -> Text {
         let mut text = Self::render_item(item, max_width, theme);
@@ -158,15 +206,16 @@ impl PluginRenderer {
             // Now indices should match the display text exactly since search matches against display text
             // But we need to handle truncation for directories
             let adjusted_indices = match item {
-                SessionItem::ExistingSession { .. } => {
+                SessionItem::ExistingSession { .. } | SessionItem::ResurrectableSession { .. } => {
                     // Indices should match the display text exactly
                     indices.to_vec()
                 }
-                SessionItem::Directory { path, .. } => {
+                SessionItem::Directory { path, worktree, .. } => {
+                    let label = directory_label(path, worktree.as_ref());
                     // Handle truncation for long paths
-                    if path.len() > max_width && max_width > 10 {
+                    if label.len() > max_width && max_width > 10 {
                         // Path is truncated with "..."
-                        let truncated_start = path.len().saturating_sub(max_width - 3);
+                        let truncated_start = label.len().saturating_sub(max_width - 3);
                         indices.iter()
                             .filter_map(|&idx| {
                                 if idx >= truncated_start {
@@ -194,12 +243,27 @@ impl PluginRenderer {
         text
     }
 
+    /// Shorten text wider than `max_width` to its first 10 and last characters around "...".
+    /// Counts characters, so multibyte names and symbols are never split.
+    fn truncate_middle(text: String, max_width: usize) -> String {
+        let len = text.chars().count();
+        if len <= max_width || max_width <= 13 {
+            return text;
+        }
+        let head: String = text.chars().take(10).collect();
+        let tail: String = text.chars().skip(len - (max_width - 13)).collect();
+        format!("{}...{}", head, tail)
+    }
+
     /// Render a session item
     fn render_item(item: &SessionItem, max_width: usize, theme: &Option<Theme>) -> Text {
         match item {
             SessionItem::ExistingSession { name, directory, is_current } => {
                 let prefix = if *is_current { "● " } else { "○ " };
-                let display_text = format!("{}{} ({})", prefix, name, directory);
+                let display_text = match directory {
+                    Some(directory) => format!("{}{} ({})", prefix, name, directory),
+                    None => format!("{}{} (no directory)", prefix, name),
+                };
                 
                 let truncated_text = if display_text.len() > max_width && max_width > 10 {
                     format!("{}...{}", &display_text[..10], &display_text[display_text.len().saturating_sub(max_width - 13)..])
@@ -223,14 +287,28 @@ impl PluginRenderer {
                     text
                 }
             }
-            SessionItem::Directory { path, .. } => {
-                let display_path = if path.len() > max_width && max_width > 10 {
-                    format!("...{}", &path[path.len().saturating_sub(max_width - 3)..])
+            SessionItem::ResurrectableSession { name, exited_ago } => {
+                let display_text = format!("↺ {} (exited {} ago)", name, format_elapsed(*exited_ago));
+                let truncated_text = Self::truncate_middle(display_text, max_width);
+                
+                if let Some(theme) = theme {
+                    theme.resurrectable_session(&truncated_text)
                 } else {
-                    path.to_string()
+                    Text::new(&truncated_text).color_range(0, ..)
+                }
+            }
+            SessionItem::Directory { path, worktree, missing, .. } => {
+                let label = directory_label(path, worktree.as_ref());
+                let display_path = if label.len() > max_width && max_width > 10 {
+                    format!("...{}", &label[label.len().saturating_sub(max_width - 3)..])
+                } else {
+                    label
                 };
                 
-                if let Some(theme) = theme {
+                if *missing {
+                    // Directories that no longer exist are dimmed
+                    Text::new(&display_path).color_range(0, ..)
+                } else if let Some(theme) = theme {
                     theme.content(&display_path)
                 } else {
                     Text::new(&display_path)
@@ -272,6 +350,11 @@ impl PluginRenderer {
         let nav_down = keybinds.format_keys_for_action(KeyAction::MoveDown);
         let select = keybinds.format_keys_for_action(KeyAction::Select);
         let delete = keybinds.format_keys_for_action(KeyAction::DeleteSession);
+        let rename = keybinds.format_keys_for_action(KeyAction::RenameSession);
+        let mark = keybinds.format_keys_for_action(KeyAction::ToggleMark);
+        let pin = keybinds.format_keys_for_action(KeyAction::TogglePin);
+        let prune = keybinds.format_keys_for_action(KeyAction::PruneSessions);
+        let previous = keybinds.format_keys_for_action(KeyAction::PreviousSession);
         let exit = keybinds.format_keys_for_action(KeyAction::Exit);
         
         // Combine up/down navigation if they're different
@@ -281,38 +364,80 @@ impl PluginRenderer {
             format!("{}/{}", nav_up, nav_down)
         };
         
-        format!(
-            "{}: Navigate • {}: Switch/Create • {}: Kill • Type: Search • {}: Exit",
-            navigation, select, delete, exit
-        )
+        let mut help = format!(
+            "{}: Navigate • {}: Switch/Create/Resurrect • {}: Previous • {}: Rename • {}: Mark • {}: Pin • {}: Kill • {}: Prune • Type: Search • {}: Exit",
+            navigation, select, previous, rename, mark, pin, delete, prune, exit
+        );
+        if state.has_missing_directories() {
+            let remove_missing = keybinds.format_keys_for_action(KeyAction::RemoveMissingDirectories);
+            help.push_str(&format!(" • {}: Remove missing", remove_missing));
+        }
+        help
     }
 
-    /// Render error message
-    fn render_error(error: &str, x: usize, y: usize, _width: usize, height: usize) {
+    /// Render error message, with its remediation hint below it
+    fn render_error(error: &str, hint: Option<&str>, x: usize, y: usize, _width: usize, height: usize) {
         let dialog_y = y + height / 2;
         let error_text = Text::new(error).color_range(1, ..);
         print_text_with_coordinates(error_text, x, dialog_y, None, None);
+        if let Some(hint) = hint {
+            let hint_text = Text::new(format!("Hint: {}", hint)).color_range(0, ..);
+            print_text_with_coordinates(hint_text, x, dialog_y + 1, None, None);
+        }
     }
 
     /// Render deletion confirmation dialog
-    fn render_deletion_confirmation(session_name: &str, x: usize, y: usize, width: usize, height: usize, _colors: Option<Palette>) {
+    fn render_deletion_confirmation(pending_deletion: &PendingDeletion, x: usize, y: usize, width: usize, height: usize, _colors: Option<Palette>) {
         let dialog_width = std::cmp::min(60, width.saturating_sub(4));
-        let dialog_height = 6;
-        let dialog_x = x + (width.saturating_sub(dialog_width)) / 2;
-        let dialog_y = y + (height.saturating_sub(dialog_height)) / 2;
+        let inner_width = dialog_width.saturating_sub(2);
         
-        let message = format!("Delete session '{}'?", session_name);
+        let (message, names) = match pending_deletion {
+            PendingDeletion::Sessions(session_names) if session_names.len() == 1 => {
+                (format!("Delete session '{}'?", session_names[0]), Vec::new())
+            }
+            PendingDeletion::Sessions(session_names) => {
+                (format!("Kill {} sessions?", session_names.len()), pending_deletion.session_names())
+            }
+            PendingDeletion::DeadSessions(session_names) if session_names.len() == 1 => {
+                (format!("Delete exited session '{}'?", session_names[0]), Vec::new())
+            }
+            PendingDeletion::DeadSessions(session_names) => {
+                (format!("Delete {} exited sessions?", session_names.len()), pending_deletion.session_names())
+            }
+        };
         let warning = "This action cannot be undone.";
         let prompt = "Press 'y' to confirm, 'n' or Esc to cancel";
         
-        let dialog_lines = vec![
-            "┌".to_string() + &"─".repeat(dialog_width.saturating_sub(2)) + "┐",
-            format!("│{:^width$}│", message, width = dialog_width.saturating_sub(2)),
-            format!("│{:^width$}│", warning, width = dialog_width.saturating_sub(2)),
-            format!("│{:^width$}│", "", width = dialog_width.saturating_sub(2)),
-            format!("│{:^width$}│", prompt, width = dialog_width.saturating_sub(2)),
-            "└".to_string() + &"─".repeat(dialog_width.saturating_sub(2)) + "┘",
+        // Leave room for the borders, message, warning, spacer and prompt
+        let max_listed = height.saturating_sub(8).max(1);
+        let mut listed_lines: Vec<String> = names
+            .iter()
+            .take(max_listed)
+            .map(|name| format!(" - {}", name))
+            .collect();
+        if names.len() > max_listed {
+            listed_lines.pop();
+            listed_lines.push(format!(" ... and {} more", names.len() - max_listed + 1));
+        }
+        
+        let mut dialog_lines = vec![
+            "┌".to_string() + &"─".repeat(inner_width) + "┐",
+            format!("│{:^width$}│", message, width = inner_width),
         ];
+        for line in listed_lines {
+            let line: String = line.chars().take(inner_width).collect();
+            dialog_lines.push(format!("│{:<width$}│", line, width = inner_width));
+        }
+        dialog_lines.extend([
+            format!("│{:^width$}│", warning, width = inner_width),
+            format!("│{:^width$}│", "", width = inner_width),
+            format!("│{:^width$}│", prompt, width = inner_width),
+            "└".to_string() + &"─".repeat(inner_width) + "┘",
+        ]);
+        
+        let dialog_height = dialog_lines.len();
+        let dialog_x = x + (width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = y + (height.saturating_sub(dialog_height)) / 2;
         
         for (i, line) in dialog_lines.iter().enumerate() {
             let text = Text::new(line).color_range(1, ..);
@@ -320,6 +445,45 @@ impl PluginRenderer {
         }
     }
 
+    /// Render inline session rename dialog
+    fn render_rename_dialog(pending_rename: &PendingRename, x: usize, y: usize, width: usize, height: usize) {
+        let dialog_width = std::cmp::min(60, width.saturating_sub(4));
+        let inner_width = dialog_width.saturating_sub(2);
+        let dialog_height = 6;
+        let dialog_x = x + (width.saturating_sub(dialog_width)) / 2;
+        let dialog_y = y + (height.saturating_sub(dialog_height)) / 2;
+        
+        let title = format!("Rename session '{}'", pending_rename.session_name);
+        let input = format!(" New name: {}_", pending_rename.new_name);
+        // Keep the cursor end of long names visible
+        let input: String = if input.chars().count() > inner_width {
+            let skip = input.chars().count() - inner_width;
+            input.chars().skip(skip).collect()
+        } else {
+            input
+        };
+        let status = pending_rename.error.clone().unwrap_or_default();
+        let prompt = "Press Enter to rename, Esc to cancel";
+        
+        let dialog_lines = [
+            "┌".to_string() + &"─".repeat(inner_width) + "┐",
+            format!("│{:^width$}│", title, width = inner_width),
+            format!("│{:<width$}│", input, width = inner_width),
+            format!("│{:^width$}│", status, width = inner_width),
+            format!("│{:^width$}│", prompt, width = inner_width),
+            "└".to_string() + &"─".repeat(inner_width) + "┘",
+        ];
+        
+        for (i, line) in dialog_lines.iter().enumerate() {
+            let text = if i == 2 {
+                Text::new(line).color_range(3, ..)
+            } else {
+                Text::new(line).color_range(1, ..)
+            };
+            print_text_with_coordinates(text, dialog_x, dialog_y + i, None, None);
+        }
+    }
+
     /// Calculate main UI size
     fn calculate_main_size(rows: usize, cols: usize) -> (usize, usize, usize, usize) {
         let width = cols;
@@ -37,6 +37,11 @@ impl Theme {
         Text::new(text).color_range(3, ..)
     }
 
+    /// Text for resurrectable (exited) session (color index 0 = dim - inactive)
+    pub fn resurrectable_session(&self, text: &str) -> Text {
+        Text::new(text).color_range(0, ..)
+    }
+
     /// Text for search highlights (color index 3 = yellow/blue)
     pub fn highlight(&self, text: Text, indices: Vec<usize>) -> Text {
         text.color_indices(3, indices)
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use zellij_tile::prelude::run_command_with_env_variables_and_cwd;
+
+use super::ZoxideError;
+
+/// Oldest zoxide release whose `query -l -s`, `add` and `remove` behave as ZSM expects
+pub const MIN_ZOXIDE_VERSION: (u32, u32, u32) = (0, 8, 0);
+
+/// How zoxide is invoked: binary, extra query arguments and environment overrides
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoxideCommand {
+    /// Path or name of the zoxide binary
+    pub binary: String,
+    /// Extra arguments appended to `zoxide query -l -s` (e.g. `--exclude /tmp`)
+    pub query_args: Vec<String>,
+    /// Environment variables set for every zoxide invocation (e.g. `_ZO_DATA_DIR`)
+    pub env: BTreeMap<String, String>,
+}
+
+impl Default for ZoxideCommand {
+    fn default() -> Self {
+        Self {
+            binary: "zoxide".to_string(),
+            query_args: Vec::new(),
+            env: BTreeMap::new(),
+        }
+    }
+}
+
+impl ZoxideCommand {
+    /// Arguments of the query listing all directories with their scores
+    pub fn query_args(&self) -> Vec<&str> {
+        let mut args = vec!["query", "-l", "-s"];
+        args.extend(self.query_args.iter().map(|arg| arg.as_str()));
+        args
+    }
+
+    /// Run zoxide with the given arguments; the result arrives as a `RunCommandResult`
+    pub fn run(&self, args: &[&str], context: BTreeMap<String, String>) {
+        let mut command = vec![self.binary.as_str()];
+        command.extend(args);
+        run_command_with_env_variables_and_cwd(&command, self.env.clone(), PathBuf::from("."), context);
+    }
+
+    /// Check the installed zoxide version; the result arrives with the `zoxide_version` context key
+    pub fn check_version(&self) {
+        let mut context = BTreeMap::new();
+        context.insert("zoxide_version".to_string(), "true".to_string());
+        self.run(&["--version"], context);
+    }
+}
+
+/// Check `zoxide --version` output (e.g. "zoxide 0.9.4") against the minimum supported version
+pub fn check_version_output(output: &str) -> Result<(), ZoxideError> {
+    let version = output.split_whitespace().nth(1).unwrap_or("").trim_start_matches('v');
+    let parts: Vec<u32> = version
+        .split(['.', '-'])
+        .take(3)
+        .map_while(|part| part.parse().ok())
+        .collect();
+
+    match parts.as_slice() {
+        [major, minor, patch] if (*major, *minor, *patch) >= MIN_ZOXIDE_VERSION => Ok(()),
+        [_, _, _] => {
+            let (major, minor, patch) = MIN_ZOXIDE_VERSION;
+            Err(ZoxideError::UnsupportedVersion(format!(
+                "found {}, need {}.{}.{} or newer",
+                version, major, minor, patch
+            )))
+        }
+        _ => Err(ZoxideError::UnsupportedVersion(format!(
+            "could not read version from '{}'",
+            output.trim()
+        ))),
+    }
+}
+
+/// Parse environment overrides written as space-separated `KEY=VALUE` pairs. Values containing
+/// spaces can be quoted with `"` or `'`, e.g. `_ZO_DATA_DIR="/Users/me/Library/Application Support"`.
+pub fn parse_env_overrides(value: &str) -> Result<BTreeMap<String, String>, String> {
+    split_quoted(value)?
+        .into_iter()
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+            _ => Err(format!("'{}' is not a KEY=VALUE pair", pair)),
+        })
+        .collect()
+}
+
+/// Split on whitespace outside of quotes, removing the quotes
+fn split_quoted(value: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    for c in value.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if let Some(q) = quote {
+        return Err(format!("unclosed {} in '{}'", q, value));
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_version_output() {
+        assert_eq!(check_version_output("zoxide 0.9.4\n"), Ok(()));
+        assert_eq!(check_version_output("zoxide v0.8.0"), Ok(()));
+        assert_eq!(check_version_output("zoxide 1.0.0-beta"), Ok(()));
+        assert!(matches!(check_version_output("zoxide 0.4.3"), Err(ZoxideError::UnsupportedVersion(_))));
+        assert!(matches!(check_version_output("garbage"), Err(ZoxideError::UnsupportedVersion(_))));
+    }
+
+    #[test]
+    fn test_parse_env_overrides() {
+        let env = parse_env_overrides("_ZO_DATA_DIR=/home/user/.zo _ZO_EXCLUDE_DIRS=").unwrap();
+
+        assert_eq!(env.get("_ZO_DATA_DIR").map(|s| s.as_str()), Some("/home/user/.zo"));
+        assert_eq!(env.get("_ZO_EXCLUDE_DIRS").map(|s| s.as_str()), Some(""));
+        assert!(parse_env_overrides("NOVALUE").is_err());
+
+        let env = parse_env_overrides(r#"_ZO_DATA_DIR="/Users/me/Library/Application Support/zoxide" A='x "y"' B="""#).unwrap();
+        assert_eq!(env["_ZO_DATA_DIR"], "/Users/me/Library/Application Support/zoxide");
+        assert_eq!(env["A"], "x \"y\"");
+        assert_eq!(env["B"], "");
+        assert!(parse_env_overrides("A=\"open").is_err());
+    }
+}
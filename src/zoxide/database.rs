@@ -0,0 +1,238 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use super::parser::{ZoxideEntry, ZoxideError};
+use crate::recency::{now, recency_weight};
+
+/// Database format version written by current zoxide releases
+const DATABASE_VERSION: u32 = 3;
+
+/// Where Zellij mounts the plugin's host folder
+const HOST_MOUNT: &str = "/host";
+
+/// Database locations relative to the host folder, for Zellij started from the home directory
+/// (Linux and macOS defaults)
+const DEFAULT_DATABASE_PATHS: &[&str] = &[
+    ".local/share/zoxide/db.zo",
+    "Library/Application Support/zoxide/db.zo",
+];
+
+/// Paths under the host mount where the zoxide database may be found: the configured path or
+/// the default locations. Relative paths are taken from the host folder (the folder Zellij was
+/// started from, which it mounts at `/host`); absolute ones must lie inside it. The plugin
+/// sandbox doesn't see the host's environment, so `HOME` and zoxide's variables can't be used.
+pub fn database_candidates(configured: Option<&str>, host_folder: &Path) -> Vec<PathBuf> {
+    let paths: Vec<&Path> = match configured {
+        Some(path) => vec![Path::new(path)],
+        None => DEFAULT_DATABASE_PATHS.iter().map(Path::new).collect(),
+    };
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let relative = if path.is_relative() { path } else { path.strip_prefix(host_folder).ok()? };
+            Some(Path::new(HOST_MOUNT).join(relative))
+        })
+        .collect()
+}
+
+/// Entries of the last database read, reused while the file's modification time is unchanged
+#[derive(Debug, Default)]
+pub struct DatabaseCache {
+    cached: Option<(PathBuf, SystemTime, Vec<ZoxideEntry>)>,
+}
+
+impl DatabaseCache {
+    /// Read the first existing database among the candidates, parsing it again only when it
+    /// changed since the last read
+    pub fn read(&mut self, candidates: &[PathBuf]) -> Result<Vec<ZoxideEntry>, ZoxideError> {
+        for path in candidates {
+            let modified = match fs::metadata(path) {
+                Ok(metadata) => metadata.modified().ok(),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    return Err(ZoxideError::DatabaseUnreadable(format!("{}: {}", path.display(), err)));
+                }
+            };
+            if let (Some((cached_path, cached_modified, entries)), Some(modified)) = (&self.cached, modified) {
+                if cached_path == path && *cached_modified == modified {
+                    return Ok(rescore(entries, now()));
+                }
+            }
+
+            let bytes = fs::read(path)
+                .map_err(|err| ZoxideError::DatabaseUnreadable(format!("{}: {}", path.display(), err)))?;
+            let entries = parse_database(&bytes, now())?;
+            self.cached = modified.map(|modified| (path.clone(), modified, entries.clone()));
+            return Ok(entries);
+        }
+        Err(ZoxideError::DatabaseUnreadable(
+            "no zoxide database found inside the folder Zellij was started from".to_string(),
+        ))
+    }
+}
+
+/// Recompute the scores of cached entries relative to `now`
+fn rescore(entries: &[ZoxideEntry], now: u64) -> Vec<ZoxideEntry> {
+    entries
+        .iter()
+        .map(|entry| match (entry.rank, entry.last_accessed) {
+            (Some(rank), Some(last_accessed)) => ZoxideEntry {
+                score: rank * recency_weight(last_accessed, now),
+                ..entry.clone()
+            },
+            _ => entry.clone(),
+        })
+        .collect()
+}
+
+/// Parse a `db.zo` file: a bincode-encoded `u32` version followed by a `u64` entry count and
+/// the entries (path as `u64` length + UTF-8 bytes, rank as `f64`, last access as `u64` epoch),
+/// all little-endian. Scores are computed like `zoxide query -s` does, relative to `now`;
+/// the raw ranks are kept alongside.
+pub fn parse_database(bytes: &[u8], now: u64) -> Result<Vec<ZoxideEntry>, ZoxideError> {
+    let mut reader = Reader { bytes, offset: 0 };
+
+    let version = reader.u32()?;
+    if version != DATABASE_VERSION {
+        return Err(ZoxideError::UnsupportedVersion(format!(
+            "database version {} (expected {})",
+            version, DATABASE_VERSION
+        )));
+    }
+
+    let count = reader.u64()?;
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let path = reader.string()?;
+        let rank = reader.f64()?;
+        if !rank.is_finite() {
+            return Err(ZoxideError::DatabaseUnreadable(format!("invalid rank {} for {}", rank, path)));
+        }
+        let last_accessed = reader.u64()?;
+        entries.push(ZoxideEntry {
+            score: rank * recency_weight(last_accessed, now),
+            rank: Some(rank),
+            path,
+            last_accessed: Some(last_accessed),
+        });
+    }
+
+    if entries.is_empty() {
+        return Err(ZoxideError::EmptyDatabase);
+    }
+    Ok(entries)
+}
+
+/// Little-endian reader over the database bytes
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], ZoxideError> {
+        let end = self.offset.checked_add(N).filter(|&end| end <= self.bytes.len());
+        let Some(end) = end else {
+            return Err(self.truncated());
+        };
+        let mut buffer = [0; N];
+        buffer.copy_from_slice(&self.bytes[self.offset..end]);
+        self.offset = end;
+        Ok(buffer)
+    }
+
+    fn u32(&mut self) -> Result<u32, ZoxideError> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64, ZoxideError> {
+        self.take().map(u64::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, ZoxideError> {
+        self.take().map(f64::from_le_bytes)
+    }
+
+    fn string(&mut self) -> Result<String, ZoxideError> {
+        let len = usize::try_from(self.u64()?).map_err(|_| self.truncated())?;
+        let end = self.offset.checked_add(len).filter(|&end| end <= self.bytes.len());
+        let Some(end) = end else {
+            return Err(self.truncated());
+        };
+        let string = std::str::from_utf8(&self.bytes[self.offset..end])
+            .map_err(|_| ZoxideError::DatabaseUnreadable(format!("invalid UTF-8 path at byte {}", self.offset)))?
+            .to_string();
+        self.offset = end;
+        Ok(string)
+    }
+
+    fn truncated(&self) -> ZoxideError {
+        ZoxideError::DatabaseUnreadable(format!("database truncated at byte {}", self.offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Time the fixtures are evaluated at: their entries were accessed 1.5 hours, 23 minutes
+    /// and about four months earlier
+    const NOW: u64 = 1_700_005_400;
+
+    #[test]
+    fn test_parse_database_fixture() {
+        let entries = parse_database(include_bytes!("../../tests/fixtures/zoxide/db_v3.zo"), NOW).unwrap();
+
+        let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, vec!["/home/user/code/zsm", "/home/user/projets/café", "/tmp/old"]);
+        assert_eq!(entries[0].last_accessed, Some(1_700_000_000));
+        assert_eq!(entries[0].score, 12.0 * 2.0);
+        assert_eq!(entries[0].rank, Some(12.0));
+        assert_eq!(entries[1].score, 3.5 * 4.0);
+        assert_eq!(entries[2].score, 8.0 * 0.25);
+
+        let later = rescore(&entries, NOW + 24 * 60 * 60);
+        assert_eq!(later[1].score, 3.5 * 0.5);
+    }
+
+    #[test]
+    fn test_parse_database_errors() {
+        assert_eq!(
+            parse_database(include_bytes!("../../tests/fixtures/zoxide/db_empty.zo"), NOW),
+            Err(ZoxideError::EmptyDatabase)
+        );
+        assert!(matches!(
+            parse_database(include_bytes!("../../tests/fixtures/zoxide/db_v2.zo"), NOW),
+            Err(ZoxideError::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            parse_database(include_bytes!("../../tests/fixtures/zoxide/db_truncated.zo"), NOW),
+            Err(ZoxideError::DatabaseUnreadable(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_database_rejects_invalid_rank() {
+        let mut bytes = DATABASE_VERSION.to_le_bytes().to_vec();
+        bytes.extend(1u64.to_le_bytes());
+        bytes.extend(2u64.to_le_bytes());
+        bytes.extend(b"/a");
+        bytes.extend(f64::NAN.to_le_bytes());
+        bytes.extend(NOW.to_le_bytes());
+
+        assert!(matches!(parse_database(&bytes, NOW), Err(ZoxideError::DatabaseUnreadable(_))));
+    }
+
+    #[test]
+    fn test_database_candidates() {
+        let host_folder = Path::new("/home/user");
+
+        assert_eq!(database_candidates(Some(".zo/db.zo"), host_folder), vec![PathBuf::from("/host/.zo/db.zo")]);
+        assert_eq!(
+            database_candidates(Some("/home/user/.zo/db.zo"), host_folder),
+            vec![PathBuf::from("/host/.zo/db.zo")]
+        );
+        assert!(database_candidates(Some("/etc/db.zo"), host_folder).is_empty());
+        assert_eq!(database_candidates(None, host_folder)[0], PathBuf::from("/host/.local/share/zoxide/db.zo"));
+    }
+}
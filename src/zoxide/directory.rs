@@ -1,14 +1,20 @@
 use serde::{Deserialize, Serialize};
 
-/// Represents a directory from zoxide with its ranking and generated session name
+/// Represents a candidate directory with its ranking, source and generated session name
 #[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq)]
 pub struct ZoxideDirectory {
     /// Zoxide ranking score (higher = more frequently used)
     pub ranking: f64,
+    /// Zoxide's raw rank before recency weighting, when read from its database
+    pub rank: Option<f64>,
     /// Full directory path
     pub directory: String,
     /// Generated session name for this directory
     pub session_name: String,
+    /// Tag of the source the directory came from (e.g. "zoxide", "ghq")
+    pub source: String,
+    /// Last access time in seconds since the Unix epoch, when known
+    pub last_accessed: Option<u64>,
 }
 
 impl Ord for ZoxideDirectory {
@@ -0,0 +1,145 @@
+use super::{DirectorySource, ZoxideDirectory};
+
+/// Filters applied to listed directories before session names are generated.
+///
+/// Patterns containing a `/` are matched against the full path (`*` and `?` stop at `/`,
+/// `**` crosses directories). Patterns without a `/` match any single path component,
+/// so `node_modules` hides every path inside a `node_modules` directory.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DirectoryFilter {
+    /// Paths matching any of these patterns are hidden
+    exclude: Vec<String>,
+    /// When not empty, only paths matching one of these patterns are listed
+    include: Vec<String>,
+    /// Zoxide entries ranked below this score are hidden
+    min_score: Option<f64>,
+}
+
+impl DirectoryFilter {
+    /// Create a filter. Patterns starting with `~/` only match once the home directory is set.
+    pub fn new(exclude: Vec<String>, include: Vec<String>, min_score: Option<f64>) -> Self {
+        Self { exclude, include, min_score }
+    }
+
+    /// Expand a leading `~/` in patterns to the home directory (read from the host, as the
+    /// plugin sandbox doesn't know it)
+    pub fn set_home(&mut self, home: &str) {
+        for pattern in self.exclude.iter_mut().chain(self.include.iter_mut()) {
+            if let Some(rest) = pattern.strip_prefix("~/") {
+                *pattern = format!("{}/{}", home.trim_end_matches('/'), rest);
+            }
+        }
+    }
+
+    /// Check if a directory passes the filter
+    pub fn allows(&self, directory: &ZoxideDirectory) -> bool {
+        let path = directory.directory.as_str();
+
+        if let Some(min_score) = self.min_score {
+            if directory.source == DirectorySource::Zoxide.tag() && directory.ranking < min_score {
+                return false;
+            }
+        }
+        if self.exclude.iter().any(|pattern| path_matches(pattern, path)) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(|pattern| path_matches(pattern, path))
+    }
+}
+
+/// Match a pattern against a path: full path patterns contain a `/`, others match any component
+pub fn path_matches(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    if pattern.contains(&'/') {
+        let path: Vec<char> = path.chars().collect();
+        glob_matches(&pattern, &path)
+    } else {
+        path.split('/').any(|component| {
+            let component: Vec<char> = component.chars().collect();
+            glob_matches(&pattern, &component)
+        })
+    }
+}
+
+/// Glob matching with `*`, `?` (neither matches `/`) and `**` (matches across `/`)
+fn glob_matches(pattern: &[char], text: &[char]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        ['*', '*', rest @ ..] => {
+            // "**/" may also match no directory at all
+            if let ['/', after_slash @ ..] = rest {
+                if glob_matches(after_slash, text) {
+                    return true;
+                }
+            }
+            (0..=text.len()).any(|skip| glob_matches(rest, &text[skip..]))
+        }
+        ['*', rest @ ..] => {
+            let segment_len = text.iter().position(|&c| c == '/').unwrap_or(text.len());
+            (0..=segment_len).any(|skip| glob_matches(rest, &text[skip..]))
+        }
+        ['?', rest @ ..] => matches!(text, [c, ..] if *c != '/') && glob_matches(rest, &text[1..]),
+        [p, rest @ ..] => matches!(text, [c, ..] if c == p) && glob_matches(rest, &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directory(path: &str, ranking: f64, source: &str) -> ZoxideDirectory {
+        ZoxideDirectory {
+            ranking,
+            rank: None,
+            directory: path.to_string(),
+            session_name: String::new(),
+            source: source.to_string(),
+            last_accessed: None,
+        }
+    }
+
+    #[test]
+    fn test_path_matches() {
+        assert!(path_matches("/tmp/*", "/tmp/build"));
+        assert!(!path_matches("/tmp/*", "/tmp/build/out"));
+        assert!(path_matches("/tmp/**", "/tmp/build/out"));
+        assert!(path_matches("/home/**/target", "/home/target"));
+        assert!(path_matches("/home/**/target", "/home/user/app/target"));
+        assert!(path_matches("/home/user/app?", "/home/user/app2"));
+        assert!(!path_matches("/home/user/app?", "/home/user/app"));
+
+        // Patterns without a slash match any path component
+        assert!(path_matches("node_modules", "/code/app/node_modules/lodash"));
+        assert!(path_matches(".git", "/code/app/.git/refs"));
+        assert!(path_matches("*.bak", "/code/old.bak/src"));
+        assert!(!path_matches("node_modules", "/code/my_node_modules_notes"));
+    }
+
+    #[test]
+    fn test_filter_allows() {
+        let filter = DirectoryFilter::new(
+            vec!["/tmp/**".to_string(), "node_modules".to_string()],
+            vec!["/code/**".to_string(), "/tmp/**".to_string()],
+            Some(1.0),
+        );
+
+        assert!(filter.allows(&directory("/code/app", 4.0, "zoxide")));
+        assert!(!filter.allows(&directory("/code/app", 0.5, "zoxide")));
+        assert!(filter.allows(&directory("/code/new", 0.0, "project")));
+        assert!(!filter.allows(&directory("/tmp/scratch", 9.0, "zoxide")));
+        assert!(!filter.allows(&directory("/code/app/node_modules/x", 9.0, "zoxide")));
+        assert!(!filter.allows(&directory("/etc/nixos", 9.0, "zoxide")));
+        assert!(DirectoryFilter::default().allows(&directory("/etc/nixos", 0.0, "zoxide")));
+    }
+
+    #[test]
+    fn test_filter_expands_home() {
+        let mut filter = DirectoryFilter::new(vec!["~/code/tmp".to_string()], vec!["~/code/**".to_string()], None);
+        assert!(!filter.allows(&directory("/home/user/code/app", 0.0, "zoxide")));
+
+        filter.set_home("/home/user/");
+        assert!(filter.allows(&directory("/home/user/code/app", 0.0, "zoxide")));
+        assert!(!filter.allows(&directory("/home/user/code/tmp", 0.0, "zoxide")));
+        assert!(!filter.allows(&directory("/home/other/code/app", 0.0, "zoxide")));
+    }
+}
@@ -0,0 +1,41 @@
+use std::collections::{BTreeMap, HashSet};
+use zellij_tile::prelude::run_command;
+
+/// Shell script printing the arguments that are not existing directories
+const MISSING_SCRIPT: &str = r#"for d in "$@"; do
+  [ -d "$d" ] || printf '%s\n' "$d"
+done"#;
+
+/// How directories that no longer exist are listed
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum MissingDirectories {
+    /// Show them dimmed, so they can be removed from zoxide
+    #[default]
+    Dim,
+    /// Leave them out of the list
+    Hide,
+}
+
+/// Check which paths no longer exist, in a single batched command. The result arrives
+/// as a `RunCommandResult` with the `missing_directories` context key.
+pub fn request_missing_check(paths: &[&str]) {
+    if paths.is_empty() {
+        return;
+    }
+    
+    let mut command = vec!["sh", "-c", MISSING_SCRIPT, "sh"];
+    command.extend(paths);
+    
+    let mut context = BTreeMap::new();
+    context.insert("missing_directories".to_string(), "true".to_string());
+    run_command(&command, context);
+}
+
+/// Parse the missing paths printed by the check
+pub fn parse_missing_output(output: &str) -> HashSet<String> {
+    output
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_string())
+        .collect()
+}
@@ -1,5 +1,24 @@
+pub mod command;
+pub mod database;
 pub mod directory;
+pub mod filter;
+pub mod missing;
+pub mod naming;
+pub mod parser;
+pub mod preview;
 pub mod search;
+pub mod sort;
+pub mod source;
+pub mod worktree;
 
+pub use command::ZoxideCommand;
 pub use directory::ZoxideDirectory;
-pub use search::SearchEngine;
\ No newline at end of file
+pub use filter::DirectoryFilter;
+pub use missing::MissingDirectories;
+pub use naming::NamingStrategy;
+pub use parser::{ZoxideEntry, ZoxideError};
+pub use preview::{PreviewCache, PreviewState};
+pub use search::SearchEngine;
+pub use sort::SortMode;
+pub use source::DirectorySource;
+pub use worktree::{GitCheckout, Worktree};
\ No newline at end of file
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use super::GitCheckout;
+use crate::session::manager::MAX_SESSION_NAME_LENGTH;
+
+/// Variables available in naming templates
+const TEMPLATE_VARIABLES: &[&str] = &["basename", "parent", "path", "repo", "branch", "sep"];
+
+/// How session names are generated for listed directories
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum NamingStrategy {
+    /// Basename, with just enough parent directories to tell conflicting or nested ones apart
+    #[default]
+    Smart,
+    /// Names rendered from a template such as `{repo}{sep}{branch}`
+    Template(String),
+}
+
+impl NamingStrategy {
+    /// Parse a built-in strategy name (basename, path, git, parent) or a template
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let template = match value {
+            "smart" => return Ok(NamingStrategy::Smart),
+            "basename" => "{basename}",
+            "path" => "{path}",
+            "git" => "{repo}{sep}{branch}",
+            "parent" => "{parent}{sep}{basename}",
+            template if template.contains('{') => template,
+            other => {
+                return Err(format!(
+                    "'{}' is not a strategy (smart, basename, path, git, parent) or a template",
+                    other
+                ))
+            }
+        };
+        validate_template(template)?;
+        Ok(NamingStrategy::Template(template.to_string()))
+    }
+}
+
+/// Values of the template variables for one directory
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NameVariables<'a> {
+    /// Last path component
+    pub basename: &'a str,
+    /// Component before the basename (empty at the root)
+    pub parent: &'a str,
+    /// Path components relative to the home directory
+    pub path: Vec<&'a str>,
+    /// Name of the git repository containing the directory
+    pub repo: Option<&'a str>,
+    /// Branch checked out in the directory
+    pub branch: Option<&'a str>,
+    /// Configured session name separator
+    pub separator: &'a str,
+}
+
+impl NameVariables<'_> {
+    /// Render a template, or None when it uses git variables outside of a repository
+    /// (or on a detached HEAD). Slashes are replaced, as session names can't contain them.
+    pub fn render(&self, template: &str) -> Option<String> {
+        let mut name = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            name.push_str(&rest[..start]);
+            let end = rest[start..].find('}')? + start;
+            let value = match &rest[start + 1..end] {
+                "basename" => self.basename.to_string(),
+                "parent" => self.parent.to_string(),
+                "path" => self.path.join(self.separator),
+                "repo" => self.repo?.to_string(),
+                "branch" => self.branch?.replace('/', "-"),
+                "sep" => self.separator.to_string(),
+                _ => return None,
+            };
+            name.push_str(&value);
+            rest = &rest[end + 1..];
+        }
+        name.push_str(rest);
+
+        let name = name.replace('/', "-");
+        (!name.is_empty()).then_some(name)
+    }
+}
+
+/// Path of a directory relative to the home directory, when inside it
+pub fn relative_to_home<'a>(path: &'a str, home: Option<&str>) -> &'a str {
+    home.map(|home| home.trim_end_matches('/'))
+        .filter(|home| !home.is_empty())
+        .and_then(|home| path.strip_prefix(home))
+        .and_then(|rest| if rest.is_empty() { Some(rest) } else { rest.strip_prefix('/') })
+        .unwrap_or(path)
+}
+
+/// Render the session name of a directory, cut to Zellij's length limit. Paths are relative
+/// to the home directory (read from the host, as the plugin sandbox doesn't know it).
+pub fn template_session_name(
+    template: &str,
+    path: &str,
+    home: Option<&str>,
+    checkout: Option<&GitCheckout>,
+    separator: &str,
+) -> Option<String> {
+    let segments: Vec<&str> = relative_to_home(path, home).split('/').filter(|s| !s.is_empty()).collect();
+    let variables = NameVariables {
+        basename: segments.last().copied().unwrap_or("root"),
+        parent: segments.len().checked_sub(2).map(|i| segments[i]).unwrap_or(""),
+        path: segments.clone(),
+        repo: checkout.map(|c| c.repository_name()),
+        branch: checkout.and_then(|c| c.branch.as_deref()),
+        separator,
+    };
+    let mut name = variables.render(template)?;
+    truncate_session_name(&mut name);
+    Some(name)
+}
+
+/// A directory's name rendered from a template, before conflicts are resolved
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedName<'a> {
+    /// Directory the name was rendered for
+    pub path: &'a str,
+    /// Rendered name
+    pub name: String,
+    /// Name the smart strategy gave the directory, used to tell conflicting ones apart
+    pub smart_name: &'a str,
+}
+
+/// Make rendered names unique. When several directories render the same name (e.g. the
+/// subdirectories of a repository with `{repo}{sep}{branch}`), the one closest to the root
+/// keeps it and the others get their smart name appended.
+pub fn resolve_conflicts(names: &mut [RenderedName], separator: &str) {
+    let mut groups: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, rendered) in names.iter().enumerate() {
+        groups.entry(rendered.name.clone()).or_default().push(i);
+    }
+
+    for mut indices in groups.into_values().filter(|indices| indices.len() > 1) {
+        indices.sort_by_key(|&i| (depth(names[i].path), names[i].path));
+        let keeps_name = depth(names[indices[0]].path) < depth(names[indices[1]].path);
+        for &i in &indices[usize::from(keeps_name)..] {
+            let mut name = format!("{}{}{}", names[i].name, separator, names[i].smart_name);
+            truncate_session_name(&mut name);
+            names[i].name = name;
+        }
+    }
+}
+
+fn depth(path: &str) -> usize {
+    path.split('/').filter(|s| !s.is_empty()).count()
+}
+
+/// Cut a session name to Zellij's length limit, on a character boundary
+fn truncate_session_name(name: &mut String) {
+    if name.len() > MAX_SESSION_NAME_LENGTH {
+        let mut end = MAX_SESSION_NAME_LENGTH;
+        while !name.is_char_boundary(end) {
+            end -= 1;
+        }
+        name.truncate(end);
+    }
+}
+
+/// Check that a template only uses known variables and closes its braces
+fn validate_template(template: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("unclosed '{{' in template '{}'", template))?
+            + start;
+        let variable = &rest[start + 1..end];
+        if !TEMPLATE_VARIABLES.contains(&variable) {
+            return Err(format!(
+                "unknown variable '{{{}}}' in template '{}' (expected one of {})",
+                variable,
+                template,
+                TEMPLATE_VARIABLES.join(", ")
+            ));
+        }
+        rest = &rest[end + 1..];
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_naming_strategy() {
+        assert_eq!(NamingStrategy::parse("smart"), Ok(NamingStrategy::Smart));
+        assert_eq!(NamingStrategy::parse("git"), Ok(NamingStrategy::Template("{repo}{sep}{branch}".to_string())));
+        assert_eq!(NamingStrategy::parse("{repo}-{basename}"), Ok(NamingStrategy::Template("{repo}-{basename}".to_string())));
+        assert!(NamingStrategy::parse("fancy").is_err());
+        assert!(NamingStrategy::parse("{project}").is_err());
+        assert!(NamingStrategy::parse("{repo").is_err());
+    }
+
+    #[test]
+    fn test_render_template() {
+        let variables = NameVariables {
+            basename: "api",
+            parent: "app",
+            path: vec!["code", "app", "api"],
+            repo: Some("app"),
+            branch: Some("feature/login"),
+            separator: ".",
+        };
+
+        assert_eq!(variables.render("{basename}"), Some("api".to_string()));
+        assert_eq!(variables.render("{path}"), Some("code.app.api".to_string()));
+        assert_eq!(variables.render("{parent}{sep}{basename}"), Some("app.api".to_string()));
+        assert_eq!(variables.render("{repo}{sep}{branch}"), Some("app.feature-login".to_string()));
+        assert_eq!(variables.render("team-{repo}"), Some("team-app".to_string()));
+
+        let outside_git = NameVariables { repo: None, branch: None, ..variables };
+        assert_eq!(outside_git.render("{repo}{sep}{branch}"), None);
+    }
+
+    #[test]
+    fn test_template_session_name() {
+        let checkout = GitCheckout { repository: "/code/app".to_string(), branch: Some("main".to_string()) };
+
+        let home = Some("/home/user");
+
+        assert_eq!(template_session_name("{path}", "/home/user/code/app/api", home, None, "."), Some("code.app.api".to_string()));
+        assert_eq!(template_session_name("{path}", "/home/username/app", home, None, "."), Some("home.username.app".to_string()));
+        assert_eq!(template_session_name("{path}", "/home/user/app", None, None, "."), Some("home.user.app".to_string()));
+        assert_eq!(template_session_name("{repo}{sep}{branch}", "/home/user/code/app/api", home, Some(&checkout), "-"), Some("app-main".to_string()));
+        assert_eq!(template_session_name("{parent}{sep}{basename}", "/srv/www", home, None, "."), Some("srv.www".to_string()));
+        assert_eq!(template_session_name("{basename}", "/", home, None, "."), Some("root".to_string()));
+        assert_eq!(template_session_name("{repo}", "/home/user/code/scratch", home, None, "."), None);
+
+        let long = template_session_name("{basename}", &"é".repeat(100), home, None, ".").unwrap();
+        assert_eq!(long.len(), MAX_SESSION_NAME_LENGTH);
+    }
+
+    #[test]
+    fn test_resolve_conflicts() {
+        let rendered = |path, name: &str, smart_name| RenderedName { path, name: name.to_string(), smart_name };
+        let mut names = vec![
+            rendered("/code/app/api", "app.main", "api"),
+            rendered("/code/app", "app.main", "app"),
+            rendered("/code/app/web", "app.main", "web"),
+            rendered("/code/lib", "lib.main", "lib"),
+            rendered("/work/a/tool", "tool", "a.tool"),
+            rendered("/home/b/tool", "tool", "b.tool"),
+        ];
+        resolve_conflicts(&mut names, ".");
+
+        let names: Vec<&str> = names.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["app.main.api", "app.main", "app.main.web", "lib.main", "tool.a.tool", "tool.b.tool"]);
+    }
+}
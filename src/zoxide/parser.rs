@@ -0,0 +1,175 @@
+use std::fmt;
+
+/// A zoxide entry, from `zoxide query -l -s` output or the database file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoxideEntry {
+    /// Zoxide ranking score
+    pub score: f64,
+    /// Directory path
+    pub path: String,
+    /// Raw rank without recency weighting (only known from the database)
+    pub rank: Option<f64>,
+    /// Last access time in seconds since the Unix epoch (only known from the database)
+    pub last_accessed: Option<u64>,
+}
+
+/// Errors reported while querying zoxide
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZoxideError {
+    /// The zoxide binary could not be run
+    NotInstalled,
+    /// zoxide ran but has no directories yet
+    EmptyDatabase,
+    /// A line of output could not be parsed (1-based line number)
+    MalformedLine { line: usize, content: String },
+    /// The installed zoxide does not support the flags or format ZSM relies on
+    UnsupportedVersion(String),
+    /// zoxide failed for another reason (its stderr)
+    CommandFailed(String),
+    /// The zoxide database file could not be found or decoded
+    DatabaseUnreadable(String),
+}
+
+impl ZoxideError {
+    /// Classify a failed zoxide invocation from its exit code and stderr
+    pub fn from_failure(exit_code: Option<i32>, stderr: &str) -> Self {
+        let stderr = stderr.trim();
+        let lowercase = stderr.to_lowercase();
+
+        // zoxide is spawned directly, so a missing binary is a failed spawn without an exit code
+        let spawn_failed = exit_code.is_none() && (lowercase.contains("os error 2") || lowercase.contains("no such file"));
+        if spawn_failed || exit_code == Some(127) {
+            ZoxideError::NotInstalled
+        } else if lowercase.contains("no match found") {
+            ZoxideError::EmptyDatabase
+        } else if ["unexpected argument", "found argument", "unrecognized", "invalid option"]
+            .iter()
+            .any(|pattern| lowercase.contains(pattern))
+        {
+            ZoxideError::UnsupportedVersion(stderr.to_string())
+        } else {
+            ZoxideError::CommandFailed(stderr.to_string())
+        }
+    }
+
+    /// How the user can fix the problem
+    pub fn hint(&self) -> &'static str {
+        match self {
+            ZoxideError::NotInstalled => {
+                "Install zoxide (https://github.com/ajeetdsouza/zoxide) and make sure it is on the PATH Zellij was started with, or set `zoxide_path`"
+            }
+            ZoxideError::EmptyDatabase => {
+                "zoxide learns directories as you cd into them; visit a few projects or run `zoxide add <path>`"
+            }
+            ZoxideError::MalformedLine { .. } | ZoxideError::UnsupportedVersion(_) => {
+                "Upgrade zoxide to a recent release (0.8 or newer)"
+            }
+            ZoxideError::CommandFailed(_) => "Check that `zoxide query -l -s` works in your shell",
+            ZoxideError::DatabaseUnreadable(_) => {
+                "Grant the RunCommands permission, or set `zoxide_database` to the path of zoxide's db.zo (it must be inside the folder Zellij was started from)"
+            }
+        }
+    }
+}
+
+impl fmt::Display for ZoxideError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZoxideError::NotInstalled => write!(f, "zoxide is not installed"),
+            ZoxideError::EmptyDatabase => write!(f, "The zoxide database is empty"),
+            ZoxideError::MalformedLine { line, content } => {
+                write!(f, "Unexpected zoxide output on line {}: '{}'", line, content)
+            }
+            ZoxideError::UnsupportedVersion(details) => write!(f, "Unsupported zoxide version: {}", details),
+            ZoxideError::CommandFailed(stderr) => write!(f, "Failed to run zoxide: {}", stderr),
+            ZoxideError::DatabaseUnreadable(details) => {
+                write!(f, "Could not read the zoxide database: {}", details)
+            }
+        }
+    }
+}
+
+/// Parse `zoxide query -l -s` output: one `score path` entry per line. Lines that can't be
+/// parsed are skipped; the first one is returned as an error to report alongside the entries,
+/// as is empty output (`EmptyDatabase`).
+pub fn parse_query_output(output: &str) -> (Vec<ZoxideEntry>, Option<ZoxideError>) {
+    let mut entries = Vec::new();
+    let mut malformed = None;
+    for (index, line) in output.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        match parse_line(line) {
+            Some(entry) => entries.push(entry),
+            None => {
+                eprintln!("Warning: Skipping unexpected zoxide output on line {}: '{}'", index + 1, line);
+                malformed.get_or_insert(ZoxideError::MalformedLine {
+                    line: index + 1,
+                    content: line.to_string(),
+                });
+            }
+        }
+    }
+
+    let error = match malformed {
+        None if entries.is_empty() => Some(ZoxideError::EmptyDatabase),
+        malformed => malformed,
+    };
+    (entries, error)
+}
+
+/// Parse a single `score path` line (the score is right-aligned by zoxide)
+fn parse_line(line: &str) -> Option<ZoxideEntry> {
+    let (score, path) = line.trim_start().split_once(' ')?;
+    let score = score.parse::<f64>().ok().filter(|score| score.is_finite())?;
+    let path = path.trim_start();
+    if path.is_empty() {
+        return None;
+    }
+
+    Some(ZoxideEntry {
+        score,
+        rank: None,
+        path: path.to_string(),
+        last_accessed: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query_output() {
+        let output = "  12.5 /home/user/code\n   0.3 /home/user/my dir\n   4.0 /srv/tab\tdir\n";
+        let (entries, error) = parse_query_output(output);
+
+        assert_eq!(error, None);
+        assert_eq!(entries, vec![
+            ZoxideEntry { score: 12.5, rank: None, path: "/home/user/code".to_string(), last_accessed: None },
+            ZoxideEntry { score: 0.3, rank: None, path: "/home/user/my dir".to_string(), last_accessed: None },
+            ZoxideEntry { score: 4.0, rank: None, path: "/srv/tab\tdir".to_string(), last_accessed: None },
+        ]);
+    }
+
+    #[test]
+    fn test_parse_query_output_skips_bad_lines() {
+        assert_eq!(parse_query_output("\n  \n"), (vec![], Some(ZoxideError::EmptyDatabase)));
+        let (entries, error) = parse_query_output("  1.0 /ok\n/missing/score\nNaN /path\n");
+        assert_eq!(entries.iter().map(|e| e.path.as_str()).collect::<Vec<_>>(), vec!["/ok"]);
+        assert_eq!(error, Some(ZoxideError::MalformedLine { line: 2, content: "/missing/score".to_string() }));
+    }
+
+    #[test]
+    fn test_classify_failure() {
+        assert_eq!(ZoxideError::from_failure(Some(127), ""), ZoxideError::NotInstalled);
+        assert_eq!(ZoxideError::from_failure(None, "No such file or directory (os error 2)"), ZoxideError::NotInstalled);
+        assert!(matches!(
+            ZoxideError::from_failure(Some(1), "Error: path not found: /gone"),
+            ZoxideError::CommandFailed(_)
+        ));
+        assert_eq!(ZoxideError::from_failure(Some(1), "zoxide: no match found\n"), ZoxideError::EmptyDatabase);
+        assert!(matches!(
+            ZoxideError::from_failure(Some(2), "error: unexpected argument '-s' found"),
+            ZoxideError::UnsupportedVersion(_)
+        ));
+        assert_eq!(ZoxideError::from_failure(Some(1), "boom"), ZoxideError::CommandFailed("boom".to_string()));
+    }
+}
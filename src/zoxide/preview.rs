@@ -0,0 +1,209 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use zellij_tile::prelude::run_command;
+
+/// Files that identify a project type, checked in order
+const PROJECT_MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "Rust"),
+    ("package.json", "Node"),
+    ("go.mod", "Go"),
+    ("pyproject.toml", "Python"),
+    ("setup.py", "Python"),
+    ("requirements.txt", "Python"),
+    ("Gemfile", "Ruby"),
+    ("pom.xml", "Java"),
+    ("build.gradle", "Java"),
+    ("build.gradle.kts", "Kotlin"),
+    ("mix.exs", "Elixir"),
+    ("composer.json", "PHP"),
+    ("CMakeLists.txt", "C/C++"),
+    ("flake.nix", "Nix"),
+    ("Makefile", "Make"),
+];
+
+/// Shell script printing git status and the directory listing in separate sections
+const PREVIEW_SCRIPT: &str = r#"cd "$1" || exit 1
+echo '## git'
+git status --porcelain=v2 --branch 2>/dev/null
+echo '## files'
+ls -A"#;
+
+/// Git state of a directory
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GitStatus {
+    /// Checked out branch (None when detached)
+    pub branch: Option<String>,
+    /// Number of changed or untracked files
+    pub changed_files: usize,
+    /// Commits ahead of upstream
+    pub ahead: usize,
+    /// Commits behind upstream
+    pub behind: usize,
+}
+
+impl GitStatus {
+    /// Check if the working tree has uncommitted changes
+    pub fn is_dirty(&self) -> bool {
+        self.changed_files > 0
+    }
+}
+
+/// Preview information for a directory
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DirectoryPreview {
+    /// Git status (None when not a git repository)
+    pub git: Option<GitStatus>,
+    /// Detected project types, from marker files
+    pub project_types: Vec<&'static str>,
+}
+
+/// State of a cached directory preview
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewState<'a> {
+    /// Preview has been requested but not received yet
+    Loading,
+    /// Preview is available
+    Ready(&'a DirectoryPreview),
+    /// The directory could not be read
+    Unavailable,
+}
+
+/// Caches directory previews per path, fetching them lazily via `run_command`
+#[derive(Debug, Default)]
+pub struct PreviewCache {
+    /// Previews by path (None when the directory could not be read)
+    previews: HashMap<String, Option<DirectoryPreview>>,
+    /// Paths whose preview has been requested but not received yet
+    loading: HashSet<String>,
+    /// Cached paths whose preview is outdated; still shown until fetched again
+    stale: HashSet<String>,
+}
+
+impl PreviewCache {
+    /// Request the preview for a path unless it is cached (and up to date) or already requested
+    pub fn request(&mut self, path: &str) {
+        if self.loading.contains(path) || (self.previews.contains_key(path) && !self.stale.contains(path)) {
+            return;
+        }
+        
+        let mut context = BTreeMap::new();
+        context.insert("directory_preview".to_string(), path.to_string());
+        run_command(&["sh", "-c", PREVIEW_SCRIPT, "sh", path], context);
+        self.loading.insert(path.to_string());
+    }
+
+    /// Store the result of a preview command (None if it failed)
+    pub fn insert(&mut self, path: &str, output: Option<&str>) {
+        self.loading.remove(path);
+        self.stale.remove(path);
+        self.previews.insert(path.to_string(), output.map(parse_preview_output));
+    }
+
+    /// Mark all cached previews as outdated, so they are fetched again when next requested
+    pub fn expire(&mut self) {
+        self.stale = self.previews.keys().cloned().collect();
+    }
+
+    /// Get the preview state for a path, if it has been requested
+    pub fn get(&self, path: &str) -> Option<PreviewState<'_>> {
+        if self.loading.contains(path) {
+            return Some(PreviewState::Loading);
+        }
+        self.previews.get(path).map(|preview| match preview {
+            Some(preview) => PreviewState::Ready(preview),
+            None => PreviewState::Unavailable,
+        })
+    }
+}
+
+/// Parse the output of the preview script
+pub fn parse_preview_output(output: &str) -> DirectoryPreview {
+    let mut preview = DirectoryPreview::default();
+    let mut files = HashSet::new();
+    let mut section = "";
+    
+    for line in output.lines() {
+        if let Some(name) = line.strip_prefix("## ") {
+            section = name;
+            continue;
+        }
+        
+        match section {
+            "git" => {
+                let git = preview.git.get_or_insert_with(GitStatus::default);
+                if let Some(head) = line.strip_prefix("# branch.head ") {
+                    git.branch = (head != "(detached)").then(|| head.to_string());
+                } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+                    for part in ab.split_whitespace() {
+                        if let Some(ahead) = part.strip_prefix('+') {
+                            git.ahead = ahead.parse().unwrap_or(0);
+                        } else if let Some(behind) = part.strip_prefix('-') {
+                            git.behind = behind.parse().unwrap_or(0);
+                        }
+                    }
+                } else if !line.starts_with('#') && !line.trim().is_empty() {
+                    git.changed_files += 1;
+                }
+            }
+            "files" => {
+                files.insert(line);
+            }
+            _ => {}
+        }
+    }
+    
+    for (marker, project_type) in PROJECT_MARKERS {
+        if files.contains(marker) && !preview.project_types.contains(project_type) {
+            preview.project_types.push(project_type);
+        }
+    }
+    
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_git_repository() {
+        let output = "## git\n\
+# branch.oid 1234abcd\n\
+# branch.head main\n\
+# branch.upstream origin/main\n\
+# branch.ab +2 -1\n\
+1 .M N... 100644 100644 100644 aaaa bbbb src/main.rs\n\
+? notes.txt\n\
+## files\n\
+.git\n\
+package.json\n\
+Cargo.toml\n\
+src\n";
+        let preview = parse_preview_output(output);
+
+        assert_eq!(preview.git, Some(GitStatus {
+            branch: Some("main".to_string()),
+            changed_files: 2,
+            ahead: 2,
+            behind: 1,
+        }));
+        assert!(preview.git.unwrap().is_dirty());
+        assert_eq!(preview.project_types, vec!["Rust", "Node"]);
+    }
+
+    #[test]
+    fn test_parse_plain_directory() {
+        let preview = parse_preview_output("## git\n## files\ngo.mod\nREADME.md\n");
+
+        assert_eq!(preview.git, None);
+        assert_eq!(preview.project_types, vec!["Go"]);
+    }
+
+    #[test]
+    fn test_parse_detached_head() {
+        let preview = parse_preview_output("## git\n# branch.oid 1234\n# branch.head (detached)\n## files\n");
+
+        let git = preview.git.unwrap();
+        assert_eq!(git.branch, None);
+        assert!(!git.is_dirty());
+    }
+}
@@ -1,6 +1,8 @@
+use std::collections::HashMap;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
-use crate::session::SessionItem;
+use crate::pins::Pin;
+use crate::session::{SessionItem, directory_label, format_elapsed};
 
 /// Search result containing an item and match information
 #[derive(Debug, Clone)]
@@ -25,6 +27,10 @@ pub struct SearchEngine {
     selected_index: Option<usize>,
     /// Whether we're currently searching
     is_searching: bool,
+    /// Pinned items, boosted ahead of all other results
+    pinned: Vec<Pin>,
+    /// Bonus added to the match score of items often selected in ZSM
+    usage_bonuses: HashMap<Pin, i64>,
 }
 
 impl Default for SearchEngine {
@@ -35,6 +41,8 @@ impl Default for SearchEngine {
             results: Vec::new(),
             selected_index: None,
             is_searching: false,
+            pinned: Vec::new(),
+            usage_bonuses: HashMap::new(),
         }
     }
 }
@@ -53,6 +61,22 @@ impl SearchEngine {
         }
     }
 
+    /// Re-run the current search on updated items, keeping the selected item selected
+    /// when it is still among the results
+    pub fn refresh(&mut self, items: &[SessionItem]) {
+        if !self.is_searching {
+            return;
+        }
+        
+        let previous = self.selected_item().cloned();
+        self.perform_search(items);
+        if let Some(previous) = previous {
+            if let Some(index) = self.results.iter().position(|r| r.item.is_same_item(&previous)) {
+                self.selected_index = Some(index);
+            }
+        }
+    }
+
     /// Add character to search term
     pub fn add_char(&mut self, c: char, items: &[SessionItem]) {
         self.search_term.push(c);
@@ -73,6 +97,16 @@ impl SearchEngine {
         self.is_searching = false;
     }
 
+    /// Set the pinned items boosted in search results
+    pub fn set_pinned(&mut self, pinned: Vec<Pin>) {
+        self.pinned = pinned;
+    }
+
+    /// Set the match score bonuses of items selected in ZSM
+    pub fn set_usage_bonuses(&mut self, usage_bonuses: HashMap<Pin, i64>) {
+        self.usage_bonuses = usage_bonuses;
+    }
+
     /// Get current search term
     pub fn search_term(&self) -> &str {
         &self.search_term
@@ -136,24 +170,22 @@ impl SearchEngine {
             
             // Match against the actual display text
             if let Some((score, indices)) = self.matcher.fuzzy_indices(&display_text, &self.search_term) {
+                let bonus = self.usage_bonuses.get(&Pin::for_item(item)).copied().unwrap_or(0);
                 matches.push(SearchResult {
                     item: item.clone(),
-                    score,
+                    score: score + bonus,
                     indices,
                 });
             }
         }
 
-        // Sort results: sessions first, then by score
+        // Sort results: pinned items first, then live sessions, then exited sessions,
+        // then directories, each by score
         matches.sort_by(|a, b| {
-            let a_is_session = a.item.is_session();
-            let b_is_session = b.item.is_session();
-            
-            match (a_is_session, b_is_session) {
-                (true, false) => std::cmp::Ordering::Less,  // a (session) comes first
-                (false, true) => std::cmp::Ordering::Greater, // b (session) comes first
-                _ => b.score.cmp(&a.score), // Same type, sort by score
-            }
+            self.is_pinned(&b.item)
+                .cmp(&self.is_pinned(&a.item))
+                .then(a.item.group_order().cmp(&b.item.group_order()))
+                .then(b.score.cmp(&a.score)) // Same type, sort by score
         });
 
         self.results = matches;
@@ -174,16 +206,27 @@ impl SearchEngine {
         }
     }
 
+    /// Check if an item is pinned
+    fn is_pinned(&self, item: &SessionItem) -> bool {
+        self.pinned.contains(&Pin::for_item(item))
+    }
+
     /// Get the display text used for searching (matches what's rendered)
     fn get_display_text_for_search(item: &SessionItem) -> String {
         match item {
             SessionItem::ExistingSession { name, directory, is_current } => {
                 let prefix = if *is_current { "● " } else { "○ " };
-                format!("{}{} ({})", prefix, name, directory)
+                match directory {
+                    Some(directory) => format!("{}{} ({})", prefix, name, directory),
+                    None => format!("{}{} (no directory)", prefix, name),
+                }
+            }
+            SessionItem::ResurrectableSession { name, exited_ago } => {
+                format!("↺ {} (exited {} ago)", name, format_elapsed(*exited_ago))
             }
-            SessionItem::Directory { path, .. } => {
+            SessionItem::Directory { path, worktree, .. } => {
                 // For directories, we search the full path as displayed
-                path.clone()
+                directory_label(path, worktree.as_ref())
             }
         }
     }
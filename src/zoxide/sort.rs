@@ -0,0 +1,151 @@
+use std::cmp::Ordering;
+
+use super::ZoxideDirectory;
+use crate::history::UsageHistory;
+use crate::recency::recency_weight;
+
+/// How listed directories are ordered
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SortMode {
+    /// By zoxide's raw rank (how often the directory was visited)
+    #[default]
+    Frequency,
+    /// Most recently accessed first
+    Recency,
+    /// Zoxide score weighted by how recently the directory was accessed
+    Frecency,
+}
+
+impl SortMode {
+    /// Parse a sort mode from its configuration name
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "frequency" => Some(SortMode::Frequency),
+            "recency" => Some(SortMode::Recency),
+            "frecency" => Some(SortMode::Frecency),
+            _ => None,
+        }
+    }
+
+    /// Name shown in the header
+    pub fn label(self) -> &'static str {
+        match self {
+            SortMode::Frequency => "frequency",
+            SortMode::Recency => "recency",
+            SortMode::Frecency => "frecency",
+        }
+    }
+
+    /// The mode switched to next
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::Frequency => SortMode::Recency,
+            SortMode::Recency => SortMode::Frecency,
+            SortMode::Frecency => SortMode::Frequency,
+        }
+    }
+
+    /// Sort directories in this mode, blending in how often and how recently they were
+    /// selected in ZSM. The sort is stable, so ties keep their source order; directories
+    /// without a known access time sort by score after those with one. Without a raw rank
+    /// from the database, zoxide's (already recency weighted) score is used instead.
+    pub fn sort(self, directories: &mut [ZoxideDirectory], history: &UsageHistory, now: u64) {
+        match self {
+            SortMode::Frequency => directories.sort_by_cached_key(|d| {
+                Descending(d.rank.unwrap_or(d.ranking) + history.directory_score(&d.directory, now))
+            }),
+            SortMode::Recency => directories.sort_by_cached_key(|d| {
+                let last_used = d.last_accessed.max(history.last_selected(&d.directory));
+                (std::cmp::Reverse(last_used), Descending(d.rank.unwrap_or(d.ranking)))
+            }),
+            SortMode::Frecency => directories.sort_by_cached_key(|d| {
+                Descending(frecency(d, now) + history.directory_score(&d.directory, now))
+            }),
+        }
+    }
+}
+
+/// Raw rank weighted once by recency; zoxide's own score already is when the rank is unknown
+fn frecency(directory: &ZoxideDirectory, now: u64) -> f64 {
+    match (directory.rank, directory.last_accessed) {
+        (Some(rank), Some(last_accessed)) => rank * recency_weight(last_accessed, now),
+        _ => directory.ranking,
+    }
+}
+
+/// Sort key ordering scores from highest to lowest
+#[derive(PartialEq)]
+struct Descending(f64);
+
+impl Eq for Descending {}
+
+impl PartialOrd for Descending {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Descending {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // A total order even for NaN (e.g. from a corrupt database), as sorting requires
+        other.0.total_cmp(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    /// A directory as read from the database (with a raw rank), or from `zoxide query`
+    /// output when its access time is unknown (score only)
+    fn directory(path: &str, rank: f64, last_accessed: Option<u64>) -> ZoxideDirectory {
+        ZoxideDirectory {
+            ranking: last_accessed.map_or(rank, |time| rank * recency_weight(time, NOW)),
+            rank: last_accessed.map(|_| rank),
+            directory: path.to_string(),
+            last_accessed,
+            ..Default::default()
+        }
+    }
+
+    fn sorted(mode: SortMode, history: &UsageHistory) -> Vec<String> {
+        let mut directories = vec![
+            directory("/often", 20.0, Some(NOW - 30 * 24 * 60 * 60)),
+            directory("/recent", 3.0, Some(NOW - 60)),
+            directory("/unknown", 10.0, None),
+            directory("/yesterday", 8.0, Some(NOW - 2 * 60 * 60)),
+        ];
+        mode.sort(&mut directories, history, NOW);
+        directories.into_iter().map(|d| d.directory).collect()
+    }
+
+    #[test]
+    fn test_sort_modes() {
+        let history = UsageHistory::default();
+        // Raw ranks 20, 10, 8 and 3, whatever zoxide's weighted scores are
+        assert_eq!(sorted(SortMode::Frequency, &history), vec!["/often", "/unknown", "/yesterday", "/recent"]);
+        assert_eq!(sorted(SortMode::Recency, &history), vec!["/recent", "/yesterday", "/often", "/unknown"]);
+        // Weighted once: 20 * 0.25 = 5, 3 * 4 = 12, 10 (zoxide's score), 8 * 2 = 16
+        assert_eq!(sorted(SortMode::Frecency, &history), vec!["/yesterday", "/recent", "/unknown", "/often"]);
+    }
+
+    #[test]
+    fn test_sort_with_nan_rank() {
+        let mut directories = vec![directory("/a", 1.0, None), directory("/corrupt", f64::NAN, Some(NOW)), directory("/b", 2.0, None)];
+        SortMode::Frequency.sort(&mut directories, &UsageHistory::default(), NOW);
+        // NaN is ordered above every score instead of breaking the sort
+        let paths: Vec<&str> = directories.iter().map(|d| d.directory.as_str()).collect();
+        assert_eq!(paths, vec!["/corrupt", "/b", "/a"]);
+    }
+
+    #[test]
+    fn test_sort_blends_usage_history() {
+        // Opened through ZSM a minute ago: 10 * 4 = 40 on top of its score
+        let history = UsageHistory::from_lines(&[format!("{}\tunknown\t/unknown", NOW - 60)]);
+        assert_eq!(sorted(SortMode::Frequency, &history), vec!["/unknown", "/often", "/yesterday", "/recent"]);
+        assert_eq!(sorted(SortMode::Recency, &history), vec!["/unknown", "/recent", "/yesterday", "/often"]);
+        assert_eq!(sorted(SortMode::Frecency, &history), vec!["/unknown", "/yesterday", "/recent", "/often"]);
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use zellij_tile::prelude::run_command_with_env_variables_and_cwd;
+
+use super::parser::{parse_query_output, ZoxideEntry, ZoxideError};
+use super::{ZoxideCommand, ZoxideDirectory};
+
+/// Shell script printing the existing directories matching each pattern (a leading `~` is expanded)
+const GLOB_SCRIPT: &str = r#"for pattern in "$@"; do
+  case "$pattern" in
+    "~") pattern="$HOME" ;;
+    "~/"*) pattern="$HOME/${pattern#??}" ;;
+  esac
+  for d in $pattern; do
+    if [ -d "$d" ]; then printf '%s\n' "$d"; fi
+  done
+done"#;
+
+/// Shell script printing each path that is an existing directory, taken literally
+/// (no word splitting or globbing; a leading `~` is expanded)
+const BOOKMARK_SCRIPT: &str = r#"for path in "$@"; do
+  case "$path" in
+    "~") path="$HOME" ;;
+    "~/"*) path="$HOME/${path#??}" ;;
+  esac
+  if [ -d "$path" ]; then printf '%s\n' "$path"; fi
+done"#;
+
+/// Shell script printing the project markers found under each root, up to the depth given as
+/// first argument (marker files sit one level below their project directory)
+const PROJECT_SCAN_SCRIPT: &str = r#"depth="$1"
+shift
+for root in "$@"; do
+  case "$root" in
+    "~") root="$HOME" ;;
+    "~/"*) root="$HOME/${root#??}" ;;
+  esac
+  find "$root" -maxdepth "$((depth + 1))" \
+    \( -name .git -print -prune \) -o \
+    \( -name node_modules -prune \) -o \
+    \( -name Cargo.toml -o -name flake.nix -o -name package.json -o -name go.mod -o -name pyproject.toml \) -print \
+    2>/dev/null
+done"#;
+
+/// A source of candidate directories, merged into the directory list
+#[derive(Debug, Clone, PartialEq)]
+pub enum DirectorySource {
+    /// Directories ranked by zoxide (`zoxide query -l -s`, run as configured by `ZoxideCommand`)
+    Zoxide,
+    /// Directories matching glob patterns such as `~/code/*/*`
+    Glob(Vec<String>),
+    /// A static list of directories from the configuration
+    Bookmarks(Vec<String>),
+    /// Project directories (containing `.git`, `Cargo.toml`, `flake.nix`, ...) found by
+    /// scanning workspace roots up to a given depth
+    Projects {
+        roots: Vec<String>,
+        depth: usize,
+        /// Ranking given to discovered projects
+        base_score: f64,
+    },
+    /// Repositories managed by ghq (`ghq list -p`)
+    Ghq,
+    /// A user command printing one path per line
+    Command(String),
+}
+
+impl DirectorySource {
+    /// Short tag shown next to directories from this source
+    pub fn tag(&self) -> &'static str {
+        match self {
+            DirectorySource::Zoxide => "zoxide",
+            DirectorySource::Glob(_) => "glob",
+            DirectorySource::Bookmarks(_) => "bookmark",
+            DirectorySource::Projects { .. } => "project",
+            DirectorySource::Ghq => "ghq",
+            DirectorySource::Command(_) => "command",
+        }
+    }
+
+    /// Command line listing the directories of this source
+    pub fn command(&self, zoxide: &ZoxideCommand) -> Vec<String> {
+        match self {
+            DirectorySource::Zoxide => {
+                let mut command = vec![zoxide.binary.clone()];
+                command.extend(to_strings(&zoxide.query_args()));
+                command
+            }
+            DirectorySource::Glob(patterns) => {
+                let mut command = to_strings(&["sh", "-c", GLOB_SCRIPT, "sh"]);
+                command.extend(patterns.iter().cloned());
+                command
+            }
+            DirectorySource::Bookmarks(paths) => {
+                let mut command = to_strings(&["sh", "-c", BOOKMARK_SCRIPT, "sh"]);
+                command.extend(paths.iter().cloned());
+                command
+            }
+            DirectorySource::Projects { roots, depth, .. } => {
+                let mut command = to_strings(&["sh", "-c", PROJECT_SCAN_SCRIPT, "sh"]);
+                command.push(depth.to_string());
+                command.extend(roots.iter().cloned());
+                command
+            }
+            DirectorySource::Ghq => to_strings(&["ghq", "list", "-p"]),
+            DirectorySource::Command(command) => to_strings(&["sh", "-c", command]),
+        }
+    }
+
+    /// Run the source's command; the result arrives as a `RunCommandResult`
+    /// with the source index under the `directory_source` context key
+    pub fn fetch(&self, index: usize, zoxide: &ZoxideCommand) {
+        let mut context = BTreeMap::new();
+        context.insert("directory_source".to_string(), index.to_string());
+        let command = self.command(zoxide);
+        let command: Vec<&str> = command.iter().map(|s| s.as_str()).collect();
+        let env = match self {
+            DirectorySource::Zoxide => zoxide.env.clone(),
+            _ => BTreeMap::new(),
+        };
+        run_command_with_env_variables_and_cwd(&command, env, PathBuf::from("."), context);
+    }
+
+    /// Parse the command output into directories (session names are generated later), along
+    /// with a problem to report (only zoxide output can have one)
+    pub fn parse_output(&self, output: &str) -> (Vec<ZoxideDirectory>, Option<ZoxideError>) {
+        let lines = output.lines().map(str::trim).filter(|line| !line.is_empty());
+        let directories = match self {
+            DirectorySource::Zoxide => {
+                let (entries, error) = parse_query_output(output);
+                return (self.entry_directories(entries), error);
+            }
+            DirectorySource::Projects { base_score, .. } => project_directories(lines)
+                .into_iter()
+                .map(|path| self.directory(*base_score, path))
+                .collect(),
+            _ => lines.map(|path| self.directory(0.0, path)).collect(),
+        };
+        (directories, None)
+    }
+
+    /// Turn zoxide entries (queried or read from the database) into directory entries
+    pub fn entry_directories(&self, entries: Vec<ZoxideEntry>) -> Vec<ZoxideDirectory> {
+        entries
+            .iter()
+            .map(|entry| ZoxideDirectory {
+                rank: entry.rank,
+                last_accessed: entry.last_accessed,
+                ..self.directory(entry.score, &entry.path)
+            })
+            .collect()
+    }
+
+    /// Build a directory entry tagged with this source
+    fn directory(&self, ranking: f64, path: &str) -> ZoxideDirectory {
+        ZoxideDirectory {
+            ranking,
+            rank: None,
+            directory: trim_trailing_slash(path).to_string(),
+            session_name: String::new(),
+            source: self.tag().to_string(),
+            last_accessed: None,
+        }
+    }
+}
+
+/// Turn project marker paths into project directories, keeping only the outermost
+/// project when projects are nested (e.g. workspace members)
+fn project_directories<'a>(markers: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut directories: Vec<&str> = markers
+        .filter_map(|marker| marker.rsplit_once('/').map(|(parent, _)| parent))
+        .filter(|parent| !parent.is_empty())
+        .collect();
+    directories.sort_unstable();
+    directories.dedup();
+    
+    let mut roots: Vec<&str> = Vec::new();
+    for directory in directories {
+        let nested = roots.iter().any(|root| {
+            directory.strip_prefix(root).is_some_and(|rest| rest.starts_with('/'))
+        });
+        if !nested {
+            roots.push(directory);
+        }
+    }
+    roots
+}
+
+/// Convert string slices to owned command arguments
+fn to_strings(args: &[&str]) -> Vec<String> {
+    args.iter().map(|s| s.to_string()).collect()
+}
+
+/// Remove a trailing slash so the same directory from different sources is merged
+fn trim_trailing_slash(path: &str) -> &str {
+    match path.strip_suffix('/') {
+        Some(trimmed) if !trimmed.is_empty() => trimmed,
+        _ => path,
+    }
+}
+
+/// Merge directories from all sources, keeping the first occurrence of each path
+/// (sources are given in priority order, zoxide first)
+pub fn merge_sources<'a>(sources: impl IntoIterator<Item = &'a [ZoxideDirectory]>) -> Vec<ZoxideDirectory> {
+    let mut seen = HashSet::new();
+    let mut merged = Vec::new();
+    for directories in sources {
+        for directory in directories {
+            if seen.insert(directory.directory.as_str()) {
+                merged.push(directory.clone());
+            }
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_zoxide_output() {
+        let (directories, error) = DirectorySource::Zoxide.parse_output("  12.5 /home/user/code\n\n  0.3 /home/user/my dir/\n");
+
+        assert_eq!(error, None);
+        assert_eq!(directories.len(), 2);
+        assert_eq!(directories[0].ranking, 12.5);
+        assert_eq!(directories[0].directory, "/home/user/code");
+        assert_eq!(directories[1].directory, "/home/user/my dir");
+        assert_eq!(directories[1].source, "zoxide");
+    }
+
+    #[test]
+    fn test_parse_path_list_output() {
+        let directories = DirectorySource::Ghq.parse_output("/home/user/ghq/github.com/a/b\n/\n").0;
+
+        assert_eq!(directories.len(), 2);
+        assert_eq!(directories[0].ranking, 0.0);
+        assert_eq!(directories[0].source, "ghq");
+        assert_eq!(directories[1].directory, "/");
+    }
+
+    #[test]
+    fn test_parse_project_markers() {
+        let source = DirectorySource::Projects { roots: vec![], depth: 3, base_score: 1.5 };
+        let output = "/code/app/.git\n/code/app/Cargo.toml\n/code/app/crates/core/Cargo.toml\n/code/nix/flake.nix\n/code/app-web/package.json\n";
+
+        let directories = source.parse_output(output).0;
+        let paths: Vec<&str> = directories.iter().map(|d| d.directory.as_str()).collect();
+
+        assert_eq!(paths, vec!["/code/app", "/code/app-web", "/code/nix"]);
+        assert!(directories.iter().all(|d| d.ranking == 1.5 && d.source == "project"));
+    }
+
+    #[test]
+    fn test_merge_sources_prefers_earlier_sources() {
+        let zoxide = DirectorySource::Zoxide.parse_output("5.0 /code/app\n").0;
+        let glob = DirectorySource::Glob(vec![]).parse_output("/code/app/\n/code/lib\n").0;
+
+        let merged = merge_sources([zoxide.as_slice(), glob.as_slice()]);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].source, "zoxide");
+        assert_eq!(merged[1].directory, "/code/lib");
+        assert_eq!(merged[1].source, "glob");
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+/// Shell script listing the git worktrees of every directory passed as an argument
+pub const WORKTREE_SCRIPT: &str = r#"for d in "$@"; do
+printf '## %s\n' "$d"
+git -C "$d" worktree list --porcelain 2>/dev/null
+done"#;
+
+/// A linked git worktree (not the main working tree of its repository)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Worktree {
+    /// Path of the repository's main working tree
+    pub main_repository: String,
+    /// Checked out branch (None when detached)
+    pub branch: Option<String>,
+}
+
+impl Worktree {
+    /// Name of the repository the worktree belongs to
+    pub fn repository_name(&self) -> &str {
+        std::path::Path::new(&self.main_repository)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(&self.main_repository)
+    }
+}
+
+/// Git repository and branch checked out at a directory (in any working tree)
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitCheckout {
+    /// Path of the repository's main working tree
+    pub repository: String,
+    /// Checked out branch (None when detached)
+    pub branch: Option<String>,
+}
+
+impl GitCheckout {
+    /// Name of the repository
+    pub fn repository_name(&self) -> &str {
+        std::path::Path::new(&self.repository)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(&self.repository)
+    }
+}
+
+/// (path, branch) of each working tree of a repository, main tree first
+type WorkingTrees = Vec<(String, Option<String>)>;
+
+/// Split the output of the worktree script into the working trees listed for each queried directory
+fn parse_working_trees(output: &str) -> Vec<(&str, WorkingTrees)> {
+    let mut queried: Vec<(&str, WorkingTrees)> = Vec::new();
+
+    for line in output.lines() {
+        if let Some(directory) = line.strip_prefix("## ") {
+            queried.push((directory, Vec::new()));
+        } else if let Some((_, listed)) = queried.last_mut() {
+            if let Some(path) = line.strip_prefix("worktree ") {
+                listed.push((path.to_string(), None));
+            } else if let Some(branch) = line.strip_prefix("branch ") {
+                if let Some((_, current)) = listed.last_mut() {
+                    *current = Some(branch.strip_prefix("refs/heads/").unwrap_or(branch).to_string());
+                }
+            }
+        }
+    }
+
+    queried
+}
+
+/// Parse the output of the worktree script into the linked worktrees among the queried
+/// directories, keyed by directory path
+pub fn parse_worktree_output(output: &str) -> HashMap<String, Worktree> {
+    let mut worktrees = HashMap::new();
+    for (directory, listed) in parse_working_trees(output) {
+        let Some((main_repository, _)) = listed.first() else {
+            continue;
+        };
+        if let Some((_, branch)) = listed.iter().skip(1).find(|(path, _)| path == directory) {
+            worktrees.insert(directory.to_string(), Worktree {
+                main_repository: main_repository.clone(),
+                branch: branch.clone(),
+            });
+        }
+    }
+    worktrees
+}
+
+/// Parse the output of the worktree script into the repository and branch of every queried
+/// directory inside a git working tree, keyed by directory path
+pub fn parse_checkout_output(output: &str) -> HashMap<String, GitCheckout> {
+    let mut checkouts = HashMap::new();
+    for (directory, listed) in parse_working_trees(output) {
+        let Some((repository, _)) = listed.first() else {
+            continue;
+        };
+        // The innermost working tree containing the directory
+        let containing = listed
+            .iter()
+            .filter(|(path, _)| std::path::Path::new(directory).starts_with(path))
+            .max_by_key(|(path, _)| path.len());
+        if let Some((_, branch)) = containing {
+            checkouts.insert(directory.to_string(), GitCheckout {
+                repository: repository.clone(),
+                branch: branch.clone(),
+            });
+        }
+    }
+    checkouts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_worktrees() {
+        let output = "\
+## /code/app
+worktree /code/app
+HEAD 1111
+branch refs/heads/main
+
+worktree /code/app-feature
+HEAD 2222
+branch refs/heads/feature/login
+
+## /code/app-feature
+worktree /code/app
+HEAD 1111
+branch refs/heads/main
+
+worktree /code/app-feature
+HEAD 2222
+branch refs/heads/feature/login
+
+worktree /code/app-hotfix
+HEAD 3333
+detached
+
+## /code/app-hotfix
+worktree /code/app
+HEAD 1111
+branch refs/heads/main
+
+worktree /code/app-hotfix
+HEAD 3333
+detached
+
+## /tmp/not-a-repo
+";
+        let worktrees = parse_worktree_output(output);
+
+        assert_eq!(worktrees.len(), 2);
+        assert_eq!(worktrees.get("/code/app-feature"), Some(&Worktree {
+            main_repository: "/code/app".to_string(),
+            branch: Some("feature/login".to_string()),
+        }));
+        assert_eq!(worktrees["/code/app-hotfix"].branch, None);
+        assert_eq!(worktrees["/code/app-hotfix"].repository_name(), "app");
+        assert!(!worktrees.contains_key("/code/app"));
+
+        let checkouts = parse_checkout_output(output);
+        assert_eq!(checkouts.len(), 3);
+        assert_eq!(checkouts["/code/app"].branch.as_deref(), Some("main"));
+        assert_eq!(checkouts["/code/app-feature"].repository_name(), "app");
+        assert_eq!(checkouts["/code/app-feature"].branch.as_deref(), Some("feature/login"));
+        assert_eq!(checkouts["/code/app-hotfix"].branch, None);
+    }
+}